@@ -0,0 +1,162 @@
+//! Derive macro for [HDF5][1].
+//!
+//! The macro implements `H5Type` and `IntoData` for structures with
+//! `repr(C)`, which allows them to be stored and loaded as they are laid out
+//! in memory. The members of the resulting compound datatype are placed at
+//! the offsets of the corresponding fields. Padding between the fields is
+//! never read; it is stored as zeros.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! #[macro_use]
+//! extern crate hdf5_derive;
+//! extern crate hdf5;
+//!
+//! #[derive(Clone, Copy, H5Type)]
+//! #[repr(C)]
+//! struct Hit {
+//!     channel: u8,
+//!     energy: f64,
+//! }
+//! ```
+//!
+//! [1]: https://www.hdfgroup.org
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
+use syn::{token, Data, DeriveInput, Error, Fields, Index, Member, Result};
+
+/// Derive `H5Type` and `IntoData`.
+#[proc_macro_derive(H5Type)]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match implement(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn implement(input: &DeriveInput) -> Result<Tokens> {
+    if !is_c(input) {
+        return Err(Error::new_spanned(&input.ident, "H5Type requires #[repr(C)]"));
+    }
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => fields.named.iter().collect::<Vec<_>>(),
+            Fields::Unnamed(ref fields) => fields.unnamed.iter().collect::<Vec<_>>(),
+            Fields::Unit => vec![],
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "H5Type supports only structures")),
+    };
+    if fields.is_empty() {
+        return Err(Error::new_spanned(&input.ident, "H5Type requires at least one field"));
+    }
+
+    let members = fields.iter().enumerate().map(|(i, field)| match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        _ => Member::Unnamed(Index::from(i)),
+    }).collect::<Vec<_>>();
+    let names = members.iter().map(|member| match *member {
+        Member::Named(ref ident) => ident.to_string(),
+        Member::Unnamed(ref index) => index.index.to_string(),
+    }).collect::<Vec<_>>();
+    let kinds = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    {
+        let clause = generics.make_where_clause();
+        for kind in &kinds {
+            clause.predicates.push(parse_quote!(#kind: ::hdf5::H5Type));
+        }
+    }
+    let (implementation, kind, clause) = generics.split_for_impl();
+    let mut borrowed = generics.clone();
+    borrowed.params.insert(0, parse_quote!('hdf5));
+    let (borrowed, _, _) = borrowed.split_for_impl();
+
+    Ok(quote! {
+        unsafe impl #implementation ::hdf5::H5Type for #name #kind #clause {
+            const PADDED: bool = ::std::mem::size_of::<Self>() != 0
+                                 #(+ ::std::mem::size_of::<#kinds>())*
+                                 #(|| <#kinds as ::hdf5::H5Type>::PADDED)*;
+
+            fn new_datatype() -> ::hdf5::Result<::hdf5::Datatype> {
+                let members = vec![#(
+                    ::hdf5::Member {
+                        name: #names.to_string(),
+                        offset: ::std::mem::offset_of!(Self, #members),
+                        datatype: <#kinds as ::hdf5::H5Type>::new_datatype()?,
+                    },
+                )*];
+                ::hdf5::Datatype::new_compound(::std::mem::size_of::<Self>(), &members)
+            }
+
+            fn is_valid(bytes: &[u8]) -> bool {
+                #({
+                    let offset = ::std::mem::offset_of!(Self, #members);
+                    let size = ::std::mem::size_of::<#kinds>();
+                    if !<#kinds as ::hdf5::H5Type>::is_valid(&bytes[offset..(offset + size)]) {
+                        return false;
+                    }
+                })*
+                true
+            }
+
+            fn store(&self, bytes: &mut [u8]) {
+                #({
+                    let offset = ::std::mem::offset_of!(Self, #members);
+                    let size = ::std::mem::size_of::<#kinds>();
+                    ::hdf5::H5Type::store(&self.#members, &mut bytes[offset..(offset + size)]);
+                })*
+            }
+        }
+
+        impl #implementation ::hdf5::IntoData for #name #kind #clause {
+            type Target = ::hdf5::Slice<'static, Self>;
+
+            #[inline]
+            fn into_data(self) -> ::hdf5::Result<Self::Target> {
+                ::hdf5::Slice::scalar(self)
+            }
+        }
+
+        impl #borrowed ::hdf5::IntoData for &'hdf5 #name #kind #clause {
+            type Target = ::hdf5::Slice<'static, #name #kind>;
+
+            #[inline]
+            fn into_data(self) -> ::hdf5::Result<Self::Target> {
+                ::hdf5::Slice::scalar(*self)
+            }
+        }
+    })
+}
+
+fn is_c(input: &DeriveInput) -> bool {
+    let mut found = false;
+    for attribute in &input.attrs {
+        if !attribute.path().is_ident("repr") {
+            continue;
+        }
+        let _ = attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                found = true;
+            }
+            if meta.input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in meta.input);
+                try!(content.parse::<Tokens>());
+            }
+            Ok(())
+        });
+    }
+    found
+}
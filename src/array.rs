@@ -0,0 +1,43 @@
+use ndarray::{self, Array, ArrayBase, ArrayView, Dimension, IxDyn};
+use std::borrow::Cow;
+
+use data::{self, Data, FromData, H5Type, IntoData, Slice};
+use Result;
+
+impl<'l, T: H5Type, D: Dimension> IntoData for ArrayView<'l, T, D> {
+    type Target = Slice<'l, T>;
+
+    fn into_data(self) -> Result<Self::Target> {
+        let dimensions = self.shape().to_vec();
+        let data = match self.to_slice() {
+            Some(data) => Cow::Borrowed(data),
+            _ => Cow::Owned(self.iter().cloned().collect()),
+        };
+        data::new_slice(data, dimensions)
+    }
+}
+
+impl<'l, T, S, D> IntoData for &'l ArrayBase<S, D>
+    where T: 'l + H5Type, S: ndarray::Data<Elem = T>, D: Dimension
+{
+    type Target = Slice<'l, T>;
+
+    #[inline]
+    fn into_data(self) -> Result<Self::Target> {
+        self.view().into_data()
+    }
+}
+
+impl<U: H5Type, D: Dimension> FromData for Array<U, D> {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        let dimensions = IxDyn(data.dimensions());
+        let array = match Array::from_shape_vec(dimensions, try!(Vec::<U>::from_data(data))) {
+            Ok(array) => array,
+            _ => raise!("the data should have the claimed number of elements"),
+        };
+        match array.into_dimensionality::<D>() {
+            Ok(array) => Ok(array),
+            _ => raise!("the data should have the requested number of dimensions"),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use ffi;
+use libc;
+use std::ffi::CStr;
+
+use data::{Buffer, Data};
+use dataspace::{self, Dataspace};
+use datatype::{self, Datatype};
+use {ID, Identity, Result};
+
+pub struct Attribute {
+    id: ID,
+}
+
+identity!(Attribute);
+
+impl Attribute {
+    pub fn read(&self) -> Result<Buffer> {
+        let datatype = try!(datatype::native(&try!(self.datatype())));
+        let space = try!(self.space());
+        let dimensions = try!(space.dimensions());
+        let mut data = vec![0; try!(datatype.size()) * product!(dimensions)];
+        ok!(ffi::H5Aread(self.id, datatype.id(), data.as_mut_ptr() as *mut _),
+            "failed to read the attribute");
+        unsafe { Buffer::adopt(data, datatype, dimensions) }
+    }
+
+    pub fn write<T: Data>(&self, data: T) -> Result<()> {
+        ok!(ffi::H5Awrite(self.id, data.datatype().id(), data.as_bytes().as_ptr() as *const _),
+            "failed to write the attribute");
+        Ok(())
+    }
+
+    pub fn datatype(&self) -> Result<Datatype> {
+        Ok(datatype::from_raw(ok!(ffi::H5Aget_type(self.id), "failed to get the datatype")))
+    }
+
+    pub fn space(&self) -> Result<Dataspace> {
+        Ok(dataspace::from_raw(ok!(ffi::H5Aget_space(self.id), "failed to get the dataspace")))
+    }
+}
+
+impl Drop for Attribute {
+    fn drop(&mut self) {
+        whatever!(ffi::H5Aclose(self.id));
+    }
+}
+
+pub fn delete<T: Identity>(object: T, name: &str) -> Result<()> {
+    ok!(ffi::H5Adelete(object.id(), str_to_cstr!(name).as_ptr()),
+        "failed to delete an attribute {:?}", name);
+    Ok(())
+}
+
+pub fn exists<T: Identity>(object: T, name: &str) -> Result<bool> {
+    let result = ok!(ffi::H5Aexists(object.id(), str_to_cstr!(name).as_ptr()),
+                     "failed to check the existence of an attribute {:?}", name);
+    Ok(result > 0)
+}
+
+pub fn names<T: Identity>(object: T) -> Result<Vec<String>> {
+    extern "C" fn collect(_: ID, name: *const libc::c_char, _: *const ffi::H5A_info_t,
+                          data: *mut libc::c_void) -> ffi::herr_t {
+        let names = unsafe { &mut *(data as *mut Vec<String>) };
+        match unsafe { CStr::from_ptr(name) }.to_str() {
+            Ok(name) => {
+                names.push(name.to_string());
+                0
+            },
+            _ => -1,
+        }
+    }
+
+    let mut names = Vec::<String>::new();
+    ok!(ffi::H5Aiterate2(object.id(), ffi::H5_INDEX_NAME, ffi::H5_ITER_INC, 0 as *mut _,
+                         Some(collect), &mut names as *mut _ as *mut _),
+        "failed to list the attributes");
+    Ok(names)
+}
+
+pub fn new<T: Identity>(object: T, name: &str, datatype: &Datatype, dataspace: &Dataspace)
+                        -> Result<Attribute> {
+
+    Ok(Attribute {
+        id: ok!(ffi::H5Acreate2(object.id(), str_to_cstr!(name).as_ptr(), datatype.id(),
+                                dataspace.id(), ffi::H5P_DEFAULT, ffi::H5P_DEFAULT),
+                "failed to create an attribute {:?}", name),
+    })
+}
+
+pub fn open<T: Identity>(object: T, name: &str) -> Result<Attribute> {
+    Ok(Attribute {
+        id: ok!(ffi::H5Aopen(object.id(), str_to_cstr!(name).as_ptr(), ffi::H5P_DEFAULT),
+                "failed to open an attribute {:?}", name),
+    })
+}
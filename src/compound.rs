@@ -0,0 +1,219 @@
+use ffi;
+use libc;
+use std::{mem, ptr, slice};
+
+use data::{self, Buffer, Data, FromData};
+use datatype::{self, Datatype, Member};
+use Result;
+
+pub struct Sequence {
+    data: Vec<u8>,
+    datatype: Option<Datatype>,
+    length: usize,
+    heap: Vec<Vec<u8>>,
+}
+
+pub struct Structure {
+    data: Vec<u8>,
+    members: Vec<Member>,
+    alignment: usize,
+    heap: Vec<Vec<u8>>,
+}
+
+impl Sequence {
+    #[inline]
+    pub fn new() -> Sequence {
+        Sequence { data: vec![], datatype: None, length: 0, heap: vec![] }
+    }
+
+    pub fn push<T: Data>(&mut self, data: T) -> Result<()> {
+        if product!(data.dimensions()) != 1 {
+            raise!("the elements of arrays should contain exactly one value each");
+        }
+        let datatype = match self.datatype.take() {
+            Some(datatype) => match try!(datatype::unify(&datatype, &data.datatype())) {
+                Some(datatype) => datatype,
+                _ => raise!("cannot mix datatypes in arrays"),
+            },
+            _ => data.datatype(),
+        };
+        self.datatype = Some(datatype);
+        try!(copy(&data, &mut self.data, &mut self.heap));
+        self.length += 1;
+        Ok(())
+    }
+
+    pub fn coagulate(self) -> Result<Buffer> {
+        let Sequence { data, datatype, length, heap } = self;
+        let datatype = match datatype {
+            Some(datatype) => datatype,
+            _ => raise!("cannot infer the datatype of empty arrays"),
+        };
+        debug_assert_eq!(length * datatype.size().unwrap(), data.len());
+        Ok(unsafe { Buffer::with_heap(data, datatype, vec![length], heap) })
+    }
+
+    /// Coagulate the elements into one fixed-size array element.
+    ///
+    /// Elements that are arrays themselves contribute their dimensions.
+    pub fn coagulate_array(self) -> Result<Buffer> {
+        let Sequence { data, datatype, length, heap } = self;
+        let datatype = match datatype {
+            Some(datatype) => datatype,
+            _ => raise!("cannot infer the datatype of empty arrays"),
+        };
+        let (base, dimensions) = if datatype::is_array(&datatype) {
+            let mut dimensions = vec![length];
+            dimensions.extend(try!(datatype.dimensions()));
+            (try!(datatype.base()), dimensions)
+        } else {
+            (datatype, vec![length])
+        };
+        let datatype = try!(datatype::new_array(&base, &dimensions));
+        Ok(unsafe { Buffer::with_heap(data, datatype, vec![], heap) })
+    }
+
+    /// Coagulate the elements into one variable-length element.
+    pub fn coagulate_variable(self) -> Result<Buffer> {
+        let Sequence { mut data, datatype, length, mut heap } = self;
+        let datatype = match datatype {
+            Some(datatype) => datatype,
+            _ => try!(datatype::new_placeholder()),
+        };
+        let sequence = ffi::hvl_t {
+            len: length as libc::size_t,
+            p: if length == 0 { ptr::null_mut() } else { data.as_mut_ptr() as *mut _ },
+        };
+        let bytes = unsafe {
+            slice::from_raw_parts(&sequence as *const _ as *const u8, mem::size_of::<ffi::hvl_t>())
+        };
+        heap.push(data);
+        let datatype = try!(datatype::new_vlen(&datatype));
+        Ok(unsafe { Buffer::with_heap(bytes.to_vec(), datatype, vec![], heap) })
+    }
+}
+
+impl Structure {
+    #[inline]
+    pub fn new() -> Structure {
+        Structure { data: vec![], members: vec![], alignment: 1, heap: vec![] }
+    }
+
+    /// Add a field placed at the next offset suitable for its datatype.
+    pub fn push<T: Data>(&mut self, name: String, data: T) -> Result<()> {
+        if product!(data.dimensions()) != 1 {
+            raise!("the field {:?} should contain exactly one element", name);
+        }
+        let datatype = data.datatype();
+        let alignment = try!(datatype::alignment(&datatype));
+        let offset = align(self.data.len(), alignment);
+        self.data.resize(offset, 0);
+        try!(copy(&data, &mut self.data, &mut self.heap));
+        self.members.push(Member { name: name, offset: offset, datatype: datatype });
+        if alignment > self.alignment {
+            self.alignment = alignment;
+        }
+        Ok(())
+    }
+
+    /// Coagulate the fields into a compound with the native alignment.
+    pub fn coagulate(self) -> Result<Buffer> {
+        let Structure { mut data, members, alignment, heap } = self;
+        let size = align(data.len(), alignment);
+        data.resize(size, 0);
+        let datatype = try!(Datatype::new_compound(size, &members));
+        Ok(unsafe { Buffer::with_heap(data, datatype, vec![], heap) })
+    }
+}
+
+/// Extract an element of an array.
+pub fn element(buffer: &Buffer, index: usize) -> Result<Buffer> {
+    let datatype = buffer.datatype();
+    let size = try!(datatype.size());
+    let data = buffer.as_bytes();
+    if (index + 1) * size > data.len() {
+        raise!("found no more elements in an array");
+    }
+    let data = data[(index * size)..((index + 1) * size)].to_vec();
+    unsafe { Buffer::new(data, datatype, vec![]) }
+}
+
+/// Expand an array or variable-length element into an array.
+///
+/// The elements of a multidimensional array are arrays of the trailing
+/// dimensions. Data of other datatypes are returned intact.
+pub fn expand(buffer: Buffer) -> Result<Buffer> {
+    let datatype = buffer.datatype();
+    if !datatype::is_array(&datatype) && !datatype::is_vlen(&datatype) {
+        return Ok(buffer);
+    }
+    if product!(buffer.dimensions()) != 1 {
+        raise!("the data should contain exactly one array");
+    }
+    let base = try!(datatype.base());
+    if datatype::is_array(&datatype) {
+        let dimensions = try!(datatype.dimensions());
+        let base = match dimensions.len() {
+            1 => base,
+            _ => try!(datatype::new_array(&base, &dimensions[1..])),
+        };
+        return unsafe { Buffer::new(buffer.as_bytes().to_vec(), base, vec![dimensions[0]]) };
+    }
+    let size = try!(base.size());
+    let sequence = unsafe { ptr::read_unaligned(buffer.as_bytes().as_ptr() as *const ffi::hvl_t) };
+    let length = sequence.len as usize;
+    let data = if length == 0 || sequence.p.is_null() {
+        vec![]
+    } else {
+        unsafe { slice::from_raw_parts(sequence.p as *const u8, length * size).to_vec() }
+    };
+    unsafe { Buffer::new(data, base, vec![length]) }
+}
+
+/// Extract a field of a structure.
+pub fn field(buffer: &Buffer, name: &str) -> Result<Option<Buffer>> {
+    let Member { datatype, offset, .. } = match try!(datatype::find_member(&buffer.datatype(),
+                                                                           name)) {
+        Some(member) => member,
+        _ => return Ok(None),
+    };
+    let size = try!(datatype.size());
+    let data = buffer.as_bytes()[offset..(offset + size)].to_vec();
+    Ok(Some(try!(unsafe { Buffer::new(data, datatype, vec![]) })))
+}
+
+/// Create a unit variant of an enum.
+pub fn variant(name: &str, value: u32) -> Result<Buffer> {
+    let datatype = try!(datatype::new_enum(&[(name.to_string(), value)]));
+    unsafe { Buffer::new(value.as_bytes().to_vec(), datatype, vec![]) }
+}
+
+/// Identify the variant of an enum among known names.
+///
+/// Enums stored as plain integers are identified by their indices.
+pub fn variant_index(buffer: Buffer, names: &[&str]) -> Result<usize> {
+    if datatype::is_enum(&buffer.datatype()) {
+        let name = try!(datatype::enum_name(&buffer.datatype(), buffer.as_bytes()));
+        return match names.iter().position(|&known| known == name) {
+            Some(index) => Ok(index),
+            _ => raise!("found an unknown enum variant {:?}", name),
+        };
+    }
+    let index = try!(usize::from_data(buffer));
+    if index >= names.len() {
+        raise!("found an unknown enum variant {}", index);
+    }
+    Ok(index)
+}
+
+#[inline]
+fn align(offset: usize, alignment: usize) -> usize {
+    (offset + alignment - 1) / alignment * alignment
+}
+
+fn copy<T: Data>(data: &T, into: &mut Vec<u8>, heap: &mut Vec<Vec<u8>>) -> Result<()> {
+    let start = into.len();
+    into.extend_from_slice(data.as_bytes());
+    unsafe { try!(data::relocate(&mut into[start..], &data.datatype(), heap)) };
+    Ok(())
+}
@@ -1,13 +1,22 @@
 use ffi;
-use std::{mem, slice};
+use libc;
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::{mem, ptr, slice};
 
-use Result;
-use datatype::{self, Datatype};
+use dataspace;
+use datatype::{self, Class, Datatype};
+use {Identity, Result};
 
-const SCALAR_DIMENSIONS: &'static [usize] = &[1];
+const SCALAR_DIMENSIONS: &'static [usize] = &[];
 
 /// An object suitable for storing.
-pub trait Data {
+///
+/// It is unsafe to implement since the raw data are passed to the library and
+/// interpreted according to the datatype and dimensions. In particular, the
+/// variable-length parts of the data should point to valid memory.
+pub unsafe trait Data {
     /// Return the raw data.
     fn as_bytes(&self) -> &[u8];
 
@@ -27,61 +36,166 @@ pub trait IntoData {
     fn into_data(self) -> Result<Self::Target>;
 }
 
-#[doc(hidden)]
-pub struct Slice<'l, T: 'l> {
+/// An object suitable for loading.
+///
+/// It is unsafe to implement for the same reasons as `Data`.
+pub unsafe trait DataMut: Data {
+    /// Return the raw data for modification.
+    fn as_bytes_mut(&mut self) -> &mut [u8];
+}
+
+/// An object capable of converting into mutable data.
+pub trait IntoDataMut {
+    /// The target type.
+    type Target: DataMut;
+
+    /// Perform the conversion.
+    fn into_data_mut(self) -> Result<Self::Target>;
+}
+
+/// An object capable of converting from data.
+pub trait FromData: Sized {
+    /// Perform the conversion.
+    fn from_data<T: Data>(data: T) -> Result<Self>;
+}
+
+/// A type stored as it is laid out in memory.
+///
+/// The trait is implemented for the primitive types and fixed-size arrays,
+/// and it can be derived for structures with `repr(C)` using `hdf5-derive`.
+/// It is unsafe to implement since the datatype should describe the layout of
+/// the type exactly, and types with padding should say so via `PADDED` and
+/// store their fields one by one.
+pub unsafe trait H5Type: Copy {
+    /// Indicate if the type contains padding bytes.
+    const PADDED: bool = false;
+
+    /// Create the datatype.
+    fn new_datatype() -> Result<Datatype>;
+
+    /// Check if the bytes represent a valid value.
+    #[inline]
+    fn is_valid(_: &[u8]) -> bool {
+        true
+    }
+
+    /// Copy the value into bytes, leaving any padding untouched.
+    #[inline]
+    fn store(&self, bytes: &mut [u8]) {
+        let size = mem::size_of::<Self>();
+        bytes[..size].copy_from_slice(unsafe {
+            slice::from_raw_parts(self as *const Self as *const u8, size)
+        });
+    }
+}
+
+/// A slice with an explicit shape.
+///
+/// By default, the elements are taken in row-major (C) order, that is, the
+/// last dimension varies the fastest.
+pub struct Shaped<'l, T: 'l> {
     data: &'l [T],
+    dimensions: Vec<usize>,
+    fortran: bool,
+}
+
+#[doc(hidden)]
+pub struct Slice<'l, T> {
+    data: Cow<'l, [u8]>,
+    datatype: Datatype,
+    dimensions: Vec<usize>,
+    phantom: PhantomData<T>,
+}
+
+#[doc(hidden)]
+pub struct SliceMut<'l, T: 'l> {
+    data: &'l mut [T],
     datatype: Datatype,
     dimensions: [usize; 1],
 }
 
+pub struct Buffer {
+    data: Vec<u8>,
+    datatype: Datatype,
+    dimensions: Vec<usize>,
+    #[allow(dead_code)]
+    heap: Vec<Vec<u8>>,
+}
+
+enum Variable {
+    Fixed,
+    String,
+    Array(usize, Box<Variable>),
+    Sequence(usize, Box<Variable>),
+    Compound(Vec<(usize, usize, Variable)>),
+}
+
 macro_rules! implement(
     ($name:ty, $datatype:expr) => (
-        impl Data for $name {
+        implement!($name, $datatype, |_| true);
+
+        impl<'l> IntoDataMut for &'l mut [$name] {
+            type Target = SliceMut<'l, $name>;
+
             #[inline]
-            fn as_bytes(&self) -> &[u8] {
-                unsafe {
-                    slice::from_raw_parts(self as *const _ as *const _, mem::size_of::<$name>())
-                }
+            fn into_data_mut(self) -> Result<Self::Target> {
+                let length = self.len();
+                Ok(SliceMut {
+                    data: self,
+                    datatype: datatype::from_raw_borrowed(unsafe { $datatype }),
+                    dimensions: [length],
+                })
             }
+        }
+
+        impl<'l> IntoDataMut for &'l mut Vec<$name> {
+            type Target = SliceMut<'l, $name>;
 
             #[inline]
-            fn datatype(&self) -> Datatype {
-                datatype::from_raw_borrowed(unsafe { $datatype })
+            fn into_data_mut(self) -> Result<Self::Target> {
+                (&mut self[..]).into_data_mut()
+            }
+        }
+    );
+    ($name:ty, $datatype:expr, $valid:expr) => (
+        unsafe impl H5Type for $name {
+            #[inline]
+            fn new_datatype() -> Result<Datatype> {
+                Ok(datatype::from_raw_borrowed(unsafe { $datatype }))
             }
 
             #[inline]
-            fn dimensions(&self) -> &[usize] {
-                SCALAR_DIMENSIONS
+            fn is_valid(bytes: &[u8]) -> bool {
+                ($valid)(bytes)
             }
         }
 
-        impl<'l> IntoData for &'l [$name] {
-            type Target = Slice<'l, $name>;
-
+        unsafe impl Data for $name {
             #[inline]
-            fn into_data(self) -> Result<Self::Target> {
-                Ok(Slice {
-                    data: self,
-                    datatype: datatype::from_raw_borrowed(unsafe { $datatype }),
-                    dimensions: [self.len()],
-                })
+            fn as_bytes(&self) -> &[u8] {
+                unsafe {
+                    slice::from_raw_parts(self as *const _ as *const _, mem::size_of::<$name>())
+                }
             }
-        }
 
-        impl<'l> IntoData for &'l Vec<$name> {
-            type Target = Slice<'l, $name>;
+            #[inline]
+            fn datatype(&self) -> Datatype {
+                datatype::from_raw_borrowed(unsafe { $datatype })
+            }
 
             #[inline]
-            fn into_data(self) -> Result<Self::Target> {
-                (self as &[$name]).into_data()
+            fn dimensions(&self) -> &[usize] {
+                SCALAR_DIMENSIONS
             }
         }
     );
 );
 
-implement!(bool, ffi::H5T_NATIVE_UINT8);
+implement!(bool, ffi::H5T_NATIVE_UINT8, |bytes: &[u8]| bytes[0] <= 1);
 
-implement!(char, ffi::H5T_NATIVE_UINT32);
+implement!(char, ffi::H5T_NATIVE_UINT32, |bytes: &[u8]| {
+    ::std::char::from_u32(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const u32) }).is_some()
+});
 
 implement!(f32, ffi::H5T_NATIVE_FLOAT);
 implement!(f64, ffi::H5T_NATIVE_DOUBLE);
@@ -108,7 +222,32 @@ implement!(isize, ffi::H5T_NATIVE_INT64);
 #[cfg(target_pointer_width = "64")]
 implement!(usize, ffi::H5T_NATIVE_UINT64);
 
-impl<'l, T: Data> Data for &'l T {
+unsafe impl<T: H5Type, const N: usize> H5Type for [T; N] {
+    const PADDED: bool = T::PADDED;
+
+    fn new_datatype() -> Result<Datatype> {
+        let datatype = try!(T::new_datatype());
+        if !datatype::is_array(&datatype) {
+            return datatype::new_array(&datatype, &[N]);
+        }
+        let mut dimensions = vec![N];
+        dimensions.extend(try!(datatype.dimensions()));
+        datatype::new_array(&try!(datatype.base()), &dimensions)
+    }
+
+    fn is_valid(bytes: &[u8]) -> bool {
+        bytes.chunks(mem::size_of::<T>()).all(T::is_valid)
+    }
+
+    fn store(&self, bytes: &mut [u8]) {
+        let size = mem::size_of::<T>();
+        for (i, value) in self.iter().enumerate() {
+            value.store(&mut bytes[(i * size)..((i + 1) * size)]);
+        }
+    }
+}
+
+unsafe impl<'l, T: Data> Data for &'l T {
     #[inline]
     fn as_bytes(&self) -> &[u8] {
         (*self).as_bytes()
@@ -125,7 +264,24 @@ impl<'l, T: Data> Data for &'l T {
     }
 }
 
-impl<'l, T> Data for Slice<'l, T> {
+unsafe impl<'l, T> Data for Slice<'l, T> {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    fn datatype(&self) -> Datatype {
+        self.datatype.clone()
+    }
+
+    #[inline]
+    fn dimensions(&self) -> &[usize] {
+        &self.dimensions
+    }
+}
+
+unsafe impl<'l, T> Data for SliceMut<'l, T> {
     #[inline]
     fn as_bytes(&self) -> &[u8] {
         unsafe {
@@ -145,6 +301,112 @@ impl<'l, T> Data for Slice<'l, T> {
     }
 }
 
+unsafe impl<'l, T> DataMut for SliceMut<'l, T> {
+    #[inline]
+    fn as_bytes_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut _,
+                                      mem::size_of::<T>() * self.data.len())
+        }
+    }
+}
+
+impl<T: H5Type> Slice<'static, T> {
+    /// Create a slice holding a single value.
+    #[doc(hidden)]
+    pub fn scalar(value: T) -> Result<Slice<'static, T>> {
+        let mut data = vec![0; mem::size_of::<T>()];
+        value.store(&mut data);
+        Ok(Slice {
+            data: Cow::Owned(data),
+            datatype: try!(T::new_datatype()),
+            dimensions: vec![],
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<'l, T> Shaped<'l, T> {
+    /// Create a slice with a shape.
+    ///
+    /// The number of elements should match the shape, which is checked upon
+    /// conversion into data.
+    pub fn new(data: &'l [T], dimensions: &[usize]) -> Shaped<'l, T> {
+        Shaped { data: data, dimensions: dimensions.to_vec(), fortran: false }
+    }
+
+    /// Take the elements in column-major (Fortran) order.
+    ///
+    /// The elements are rearranged into row-major order, which is the one of
+    /// HDF5, so that the stored dataset has the same shape.
+    pub fn fortran(mut self) -> Shaped<'l, T> {
+        self.fortran = true;
+        self
+    }
+}
+
+impl Buffer {
+    /// Create a buffer.
+    ///
+    /// The variable-length parts of the data are copied into the buffer. The
+    /// function is unsafe since the data should match the datatype, and the
+    /// variable-length parts should point to valid memory.
+    pub unsafe fn new(mut data: Vec<u8>, datatype: Datatype, dimensions: Vec<usize>)
+                      -> Result<Buffer> {
+
+        let mut heap = vec![];
+        try!(relocate(&mut data, &datatype, &mut heap));
+        Ok(Buffer::with_heap(data, datatype, dimensions, heap))
+    }
+
+    /// Create a buffer taking over the data read by the library.
+    ///
+    /// The variable-length parts of the data are copied into the buffer, and
+    /// the memory allocated by the library is released, also when the copying
+    /// fails. The function is unsafe for the same reasons as `Buffer::new`.
+    pub unsafe fn adopt(mut data: Vec<u8>, datatype: Datatype, dimensions: Vec<usize>)
+                        -> Result<Buffer> {
+
+        if let Ok(Variable::Fixed) = variable(&datatype) {
+            return Ok(Buffer::with_heap(data, datatype, dimensions, vec![]));
+        }
+        let buffer = Buffer::new(data.clone(), datatype.clone(), dimensions.clone());
+        let space = try!(dataspace::new(&dimensions));
+        ok!(ffi::H5Dvlen_reclaim(datatype.id(), space.id(), ffi::H5P_DEFAULT,
+                                 data.as_mut_ptr() as *mut _),
+            "failed to release variable-length data");
+        buffer
+    }
+
+    /// Create a buffer whose variable-length parts are kept in a heap.
+    ///
+    /// The function is unsafe since the data should match the datatype, and
+    /// the variable-length parts should point to valid memory.
+    #[inline]
+    pub unsafe fn with_heap(data: Vec<u8>, datatype: Datatype, dimensions: Vec<usize>,
+                     heap: Vec<Vec<u8>>) -> Buffer {
+
+        Buffer { data: data, datatype: datatype, dimensions: dimensions, heap: heap }
+    }
+}
+
+unsafe impl Data for Buffer {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    fn datatype(&self) -> Datatype {
+        self.datatype.clone()
+    }
+
+    #[inline]
+    fn dimensions(&self) -> &[usize] {
+        &self.dimensions
+    }
+}
+
 impl<T: Data> IntoData for T {
     type Target = T;
 
@@ -154,12 +416,310 @@ impl<T: Data> IntoData for T {
     }
 }
 
+impl<'l, T: H5Type> IntoData for &'l [T] {
+    type Target = Slice<'l, T>;
+
+    #[inline]
+    fn into_data(self) -> Result<Self::Target> {
+        new_slice(Cow::Borrowed(self), vec![self.len()])
+    }
+}
+
+impl<'l, T: H5Type> IntoData for &'l Vec<T> {
+    type Target = Slice<'l, T>;
+
+    #[inline]
+    fn into_data(self) -> Result<Self::Target> {
+        (self as &[T]).into_data()
+    }
+}
+
+impl<'l, T: H5Type> IntoData for &'l Vec<Vec<T>> {
+    type Target = Slice<'l, T>;
+
+    fn into_data(self) -> Result<Self::Target> {
+        let columns = self.first().map_or(0, |row| row.len());
+        if self.iter().any(|row| row.len() != columns) {
+            raise!("the rows should have the same number of elements");
+        }
+        let data = self.iter().flat_map(|row| row.iter().cloned()).collect::<Vec<_>>();
+        new_slice(Cow::Owned(data), vec![self.len(), columns])
+    }
+}
+
+impl<'l, T: H5Type, const N: usize, const M: usize> IntoData for &'l [[T; N]; M] {
+    type Target = Slice<'l, T>;
+
+    fn into_data(self) -> Result<Self::Target> {
+        let data = unsafe { slice::from_raw_parts(self.as_ptr() as *const T, M * N) };
+        new_slice(Cow::Borrowed(data), vec![M, N])
+    }
+}
+
+impl<'l, T: H5Type> IntoData for Shaped<'l, T> {
+    type Target = Slice<'l, T>;
+
+    fn into_data(self) -> Result<Self::Target> {
+        if product!(self.dimensions) != self.data.len() {
+            raise!("the data should have as many elements as the shape");
+        }
+        let data = if self.fortran && self.dimensions.len() > 1 {
+            Cow::Owned(transpose(self.data, &self.dimensions))
+        } else {
+            Cow::Borrowed(self.data)
+        };
+        new_slice(data, self.dimensions)
+    }
+}
+
 impl<'l> IntoData for &'l str {
     type Target = Slice<'l, u8>;
 
     #[inline]
     fn into_data(self) -> Result<Self::Target> {
-        let datatype = try!(datatype::new_string(self.len()));
-        Ok(Slice { data: self.as_bytes(), datatype: datatype, dimensions: [1] })
+        let data = if self.is_empty() { &b"\0"[..] } else { self.as_bytes() };
+        let datatype = try!(datatype::new_string(data.len()));
+        Ok(Slice {
+            data: Cow::Borrowed(data),
+            datatype: datatype,
+            dimensions: vec![],
+            phantom: PhantomData,
+        })
     }
 }
+
+macro_rules! strings(
+    ($($name:ty),*) => ($(
+        impl<'l> IntoData for $name {
+            type Target = Buffer;
+
+            fn into_data(self) -> Result<Self::Target> {
+                let mut data = Vec::with_capacity(self.len() * mem::size_of::<usize>());
+                let mut heap = Vec::with_capacity(self.len());
+                for value in self.iter() {
+                    let value = match CString::new(&value[..]) {
+                        Ok(value) => value.into_bytes_with_nul(),
+                        _ => raise!("the strings should not contain null characters"),
+                    };
+                    data.extend_from_slice((value.as_ptr() as usize).as_bytes());
+                    heap.push(value);
+                }
+                let datatype = try!(datatype::new_variable_string());
+                Ok(unsafe { Buffer::with_heap(data, datatype, vec![self.len()], heap) })
+            }
+        }
+    )*);
+);
+
+strings!(&'l [String], &'l Vec<String>, &'l [&'l str], &'l Vec<&'l str>);
+
+impl<U: H5Type> FromData for U {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        if product!(data.dimensions()) != 1 {
+            raise!("the data should contain exactly one element");
+        }
+        let mut values = try!(Vec::<U>::from_data(data));
+        Ok(values.pop().unwrap())
+    }
+}
+
+impl<U: H5Type> FromData for Vec<U> {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        if data.datatype() != try!(U::new_datatype()) {
+            raise!("the data should have the datatype of the requested type");
+        }
+        let bytes = data.as_bytes();
+        let size = mem::size_of::<U>();
+        if bytes.len() != size * product!(data.dimensions()) {
+            raise!("the data should have the claimed number of elements");
+        }
+        if !bytes.chunks(size).all(U::is_valid) {
+            raise!("the data should contain valid values of the requested type");
+        }
+        Ok(unsafe { copy(bytes) })
+    }
+}
+
+impl FromData for String {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        if product!(data.dimensions()) != 1 {
+            raise!("the data should contain exactly one string");
+        }
+        let mut values = try!(Vec::<String>::from_data(data));
+        Ok(values.pop().unwrap())
+    }
+}
+
+impl FromData for Vec<String> {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        let datatype = data.datatype();
+        if !datatype::is_string(&datatype) {
+            raise!("the data should have a string datatype");
+        }
+        let count = product!(data.dimensions());
+        let bytes = data.as_bytes();
+        let size = try!(datatype.size());
+        if bytes.len() != size * count {
+            raise!("the data should have the claimed number of elements");
+        }
+        let variable = datatype::is_variable_string(&datatype);
+        let mut values = Vec::with_capacity(count);
+        for bytes in bytes.chunks(size) {
+            let bytes = if variable {
+                unsafe {
+                    let pointer = bytes.as_ptr() as *const *const libc::c_char;
+                    let pointer = ptr::read_unaligned(pointer);
+                    if pointer.is_null() { &[] } else { CStr::from_ptr(pointer).to_bytes() }
+                }
+            } else {
+                &bytes[..bytes.iter().position(|&byte| byte == 0).unwrap_or(bytes.len())]
+            };
+            match String::from_utf8(bytes.to_vec()) {
+                Ok(value) => values.push(value),
+                _ => raise!("the data should contain valid UTF-8 strings"),
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Create a slice of data with given dimensions.
+///
+/// Values with padding are copied field by field into zeroed bytes, so that
+/// the padding is never read.
+pub fn new_slice<'l, T: H5Type>(data: Cow<'l, [T]>, dimensions: Vec<usize>)
+                                -> Result<Slice<'l, T>> {
+
+    let size = mem::size_of::<T>();
+    let data = match data {
+        Cow::Borrowed(data) if !T::PADDED => Cow::Borrowed(unsafe {
+            slice::from_raw_parts(data.as_ptr() as *const u8, size * data.len())
+        }),
+        data => {
+            let mut bytes = vec![0; size * data.len()];
+            for (i, value) in data.iter().enumerate() {
+                value.store(&mut bytes[(i * size)..((i + 1) * size)]);
+            }
+            Cow::Owned(bytes)
+        },
+    };
+    Ok(Slice {
+        data: data,
+        datatype: try!(T::new_datatype()),
+        dimensions: dimensions,
+        phantom: PhantomData,
+    })
+}
+
+/// Copy the variable-length parts of the data into a heap.
+///
+/// The pointers in the data are updated to refer to the copies, which stay
+/// valid as long as the heap is alive.
+pub unsafe fn relocate(data: &mut [u8], datatype: &Datatype, heap: &mut Vec<Vec<u8>>)
+                       -> Result<()> {
+
+    let variable = try!(variable(datatype));
+    if let Variable::Fixed = variable {
+        return Ok(());
+    }
+    relocate_elements(data, try!(datatype.size()), &variable, heap);
+    Ok(())
+}
+
+unsafe fn relocate_elements(data: &mut [u8], size: usize, variable: &Variable,
+                            heap: &mut Vec<Vec<u8>>) {
+
+    for element in data.chunks_mut(size) {
+        match *variable {
+            Variable::Fixed => {},
+            Variable::String => {
+                let pointer = element.as_mut_ptr() as *mut *const libc::c_char;
+                if ptr::read_unaligned(pointer).is_null() {
+                    continue;
+                }
+                let value = CStr::from_ptr(ptr::read_unaligned(pointer)).to_bytes_with_nul();
+                let value = value.to_vec();
+                ptr::write_unaligned(pointer, value.as_ptr() as *const _);
+                heap.push(value);
+            },
+            Variable::Array(size, ref variable) => {
+                relocate_elements(element, size, variable, heap);
+            },
+            Variable::Sequence(size, ref variable) => {
+                let pointer = element.as_mut_ptr() as *mut ffi::hvl_t;
+                let mut sequence = ptr::read_unaligned(pointer);
+                if sequence.len == 0 || sequence.p.is_null() {
+                    continue;
+                }
+                let length = sequence.len as usize * size;
+                let mut value = slice::from_raw_parts(sequence.p as *const u8, length).to_vec();
+                relocate_elements(&mut value, size, variable, heap);
+                sequence.p = value.as_mut_ptr() as *mut _;
+                ptr::write_unaligned(pointer, sequence);
+                heap.push(value);
+            },
+            Variable::Compound(ref members) => {
+                for &(offset, size, ref variable) in members {
+                    relocate_elements(&mut element[offset..(offset + size)], size, variable,
+                                      heap);
+                }
+            },
+        }
+    }
+}
+
+fn variable(datatype: &Datatype) -> Result<Variable> {
+    Ok(match try!(datatype.class()) {
+        Class::String if datatype::is_variable_string(datatype) => Variable::String,
+        Class::Array => {
+            let base = try!(datatype.base());
+            match try!(variable(&base)) {
+                Variable::Fixed => Variable::Fixed,
+                variable => Variable::Array(try!(base.size()), Box::new(variable)),
+            }
+        },
+        Class::Vlen => {
+            let base = try!(datatype.base());
+            Variable::Sequence(try!(base.size()), Box::new(try!(variable(&base))))
+        },
+        Class::Compound => {
+            let mut variables = vec![];
+            for member in try!(datatype.members()) {
+                let variable = try!(variable(&member.datatype));
+                if let Variable::Fixed = variable {
+                    continue;
+                }
+                variables.push((member.offset, try!(member.datatype.size()), variable));
+            }
+            if variables.is_empty() { Variable::Fixed } else { Variable::Compound(variables) }
+        },
+        _ => Variable::Fixed,
+    })
+}
+
+fn transpose<T: Copy>(data: &[T], dimensions: &[usize]) -> Vec<T> {
+    let mut result = Vec::with_capacity(data.len());
+    let mut position = vec![0; dimensions.len()];
+    for _ in 0..data.len() {
+        let offset = (0..dimensions.len()).rev().fold(0, |offset, i| {
+            offset * dimensions[i] + position[i]
+        });
+        result.push(data[offset]);
+        for i in (0..dimensions.len()).rev() {
+            position[i] += 1;
+            if position[i] < dimensions[i] {
+                break;
+            }
+            position[i] = 0;
+        }
+    }
+    result
+}
+
+unsafe fn copy<T>(bytes: &[u8]) -> Vec<T> {
+    let length = bytes.len() / mem::size_of::<T>();
+    let mut values = Vec::with_capacity(length);
+    ptr::copy_nonoverlapping(bytes.as_ptr(), values.as_mut_ptr() as *mut u8, bytes.len());
+    values.set_len(length);
+    values
+}
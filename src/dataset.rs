@@ -1,29 +1,173 @@
 use ffi;
+use libc;
 
-use data::Data;
-use dataspace::{self, Dataspace};
-use datatype::Datatype;
+use data::{Buffer, Data, DataMut, FromData, IntoData};
+use dataspace::{self, Dataspace, Hyperslab, Operator};
+use datatype::{self, Datatype};
+use options::{self, Options};
+use plist::{self, PropertyList};
 use {ID, Identity, Location, Result};
 
+/// A dataset.
 pub struct Dataset {
     id: ID,
 }
 
+/// A storage layout.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Layout {
+    /// The data are stored in the object header.
+    Compact,
+    /// The data are stored in one contiguous block.
+    Contiguous,
+    /// The data are stored in separate chunks.
+    Chunked,
+}
+
 identity!(Dataset);
+object!(Dataset);
 
 impl Dataset {
-    pub fn write<T: Data>(&self, data: T, memory_space: &Dataspace, file_space: &Dataspace)
-                          -> Result<()> {
+    /// Open an existing dataset.
+    pub fn open<T: Location>(location: T, name: &str) -> Result<Dataset> {
+        Ok(Dataset {
+            id: ok!(ffi::H5Dopen2(location.id(), str_to_cstr!(name).as_ptr(), ffi::H5P_DEFAULT),
+                    "failed to open a dataset {:?}", name),
+        })
+    }
+
+    /// Return the dimensions of the chunks if the layout is chunked.
+    pub fn chunk(&self) -> Result<Option<Vec<usize>>> {
+        if try!(self.layout()) != Layout::Chunked {
+            return Ok(None);
+        }
+        let list = try!(self.creation());
+        let count = try!(self.dimensions()).len();
+        let mut dimensions = vec![0 as ffi::hsize_t; count];
+        ok!(ffi::H5Pget_chunk(list.id(), count as libc::c_int, dimensions.as_mut_ptr()),
+            "failed to obtain the chunk dimensions");
+        Ok(Some(dimensions.into_iter().map(|dimension| dimension as usize).collect()))
+    }
+
+    /// Return the datatype of the elements.
+    pub fn datatype(&self) -> Result<Datatype> {
+        Ok(datatype::from_raw(ok!(ffi::H5Dget_type(self.id), "failed to get the datatype")))
+    }
+
+    /// Return the dimensions.
+    #[inline]
+    pub fn dimensions(&self) -> Result<Vec<usize>> {
+        try!(self.space()).dimensions()
+    }
+
+    /// Return the storage layout.
+    pub fn layout(&self) -> Result<Layout> {
+        let list = try!(self.creation());
+        Ok(match unsafe { ffi::H5Pget_layout(list.id()) } {
+            ffi::H5D_COMPACT => Layout::Compact,
+            ffi::H5D_CONTIGUOUS => Layout::Contiguous,
+            ffi::H5D_CHUNKED => Layout::Chunked,
+            _ => raise!("failed to obtain the storage layout"),
+        })
+    }
+
+    /// Return the maximal dimensions.
+    ///
+    /// Unlimited dimensions are reported as `None`.
+    #[inline]
+    pub fn max_dimensions(&self) -> Result<Vec<Option<usize>>> {
+        try!(self.space()).max_dimensions()
+    }
+
+    /// Return the amount of storage in bytes allocated in the file.
+    #[inline]
+    pub fn storage_size(&self) -> Result<usize> {
+        Ok(unsafe { ffi::H5Dget_storage_size(self.id) } as usize)
+    }
 
-        ok!(ffi::H5Dwrite(self.id, data.datatype().id(), memory_space.id(), file_space.id(),
-                          ffi::H5P_DEFAULT, data.as_bytes().as_ptr() as *const _),
-            "failed to write the data");
+    /// Read a hyperslab.
+    ///
+    /// The elements are read as an array whose dimensions are those of the
+    /// hyperslab.
+    pub fn read_hyperslab<T: FromData>(&self, hyperslab: &Hyperslab) -> Result<T> {
+        let selection = try!(self.space());
+        try!(selection.select_hyperslab(Operator::Set, hyperslab));
+        self.read_space(&selection, hyperslab.dimensions())
+    }
+
+    /// Read the selected elements.
+    ///
+    /// The selection should be made on a dataspace with the dimensions of the
+    /// dataset, such as the one returned by `space`. The elements are read as
+    /// a one-dimensional array.
+    pub fn read_selection<T: FromData>(&self, selection: &Dataspace) -> Result<T> {
+        if try!(selection.dimensions()) != try!(self.dimensions()) {
+            raise!("the selection should have the dimensions of the dataset");
+        }
+        self.read_space(selection, vec![try!(selection.selection_size())])
+    }
+
+    /// Change the dimensions.
+    ///
+    /// The dataset should have a chunked layout, and the new dimensions should
+    /// not exceed the maximal ones.
+    pub fn resize(&self, dimensions: &[usize]) -> Result<()> {
+        let max_dimensions = try!(self.max_dimensions());
+        if dimensions.len() != max_dimensions.len() {
+            raise!("the dimensions should have the number of dimensions of the dataset");
+        }
+        for (&dimension, &max_dimension) in dimensions.iter().zip(&max_dimensions) {
+            if max_dimension.map_or(false, |max_dimension| dimension > max_dimension) {
+                raise!("the dimensions should not exceed the maximal ones");
+            }
+        }
+        let dimensions = dimensions.iter().map(|&dimension| dimension as ffi::hsize_t)
+                                          .collect::<Vec<_>>();
+        ok!(ffi::H5Dset_extent(self.id, dimensions.as_ptr()), "failed to resize the dataset");
         Ok(())
     }
 
+    /// Write the selected elements.
+    ///
+    /// The selection should be made on a dataspace with the dimensions of the
+    /// dataset, such as the one returned by `space`. The elements are taken in
+    /// order from the data, whose number of elements should match the number
+    /// of selected ones.
+    pub fn write_selection<T: IntoData>(&self, data: T, selection: &Dataspace) -> Result<()> {
+        if try!(selection.dimensions()) != try!(self.dimensions()) {
+            raise!("the selection should have the dimensions of the dataset");
+        }
+        let data = try!(data.into_data());
+        let count = try!(selection.selection_size());
+        if product!(data.dimensions()) != count {
+            raise!("the data should have as many elements as selected");
+        }
+        write(self, data, &try!(dataspace::new(&[count])), selection)
+    }
+
+    /// Return the dataspace.
+    ///
+    /// All the elements are selected.
     pub fn space(&self) -> Result<Dataspace> {
         Ok(dataspace::from_raw(ok!(ffi::H5Dget_space(self.id), "failed to get the dataspace")))
     }
+
+    fn read_space<T: FromData>(&self, selection: &Dataspace, dimensions: Vec<usize>)
+                               -> Result<T> {
+
+        let datatype = try!(datatype::native(&try!(self.datatype())));
+        let memory_space = try!(dataspace::new(&dimensions));
+        let mut data = vec![0; try!(datatype.size()) * product!(dimensions)];
+        ok!(ffi::H5Dread(self.id, datatype.id(), memory_space.id(), selection.id(),
+                         ffi::H5P_DEFAULT, data.as_mut_ptr() as *mut _),
+            "failed to read the data");
+        T::from_data(try!(unsafe { Buffer::adopt(data, datatype, dimensions) }))
+    }
+
+    fn creation(&self) -> Result<PropertyList> {
+        Ok(plist::from_raw(ok!(ffi::H5Dget_create_plist(self.id),
+                               "failed to get the creation properties")))
+    }
 }
 
 impl Drop for Dataset {
@@ -32,13 +176,58 @@ impl Drop for Dataset {
     }
 }
 
-pub fn new<T: Location>(location: T, name: &str, datatype: &Datatype, dataspace: &Dataspace)
-                        -> Result<Dataset> {
+pub fn new<T: Location>(location: T, name: &str, datatype: &Datatype, dataspace: &Dataspace,
+                        options: &Options) -> Result<Dataset> {
 
+    let link = try!(plist::new_link_create());
+    let creation = try!(options::new_dataset_create(options, dataspace));
+    let datatype = try!(options::new_file_datatype(options, datatype));
     Ok(Dataset {
         id: ok!(ffi::H5Dcreate2(location.id(), str_to_cstr!(name).as_ptr(), datatype.id(),
-                                dataspace.id(), ffi::H5P_DEFAULT, ffi::H5P_DEFAULT,
-                                ffi::H5P_DEFAULT),
+                                dataspace.id(), link.id(), creation.id(), ffi::H5P_DEFAULT),
                 "failed to create a dataset {:?}", name),
     })
 }
+
+pub fn read(dataset: &Dataset, datatype: &Datatype) -> Result<Buffer> {
+    let dimensions = try!(dataset.dimensions());
+    let mut data = vec![0; try!(datatype.size()) * product!(dimensions)];
+    ok!(ffi::H5Dread(dataset.id, datatype.id(), ffi::H5S_ALL, ffi::H5S_ALL, ffi::H5P_DEFAULT,
+                     data.as_mut_ptr() as *mut _),
+        "failed to read the data");
+    unsafe { Buffer::adopt(data, datatype.clone(), dimensions) }
+}
+
+pub fn read_into<T: DataMut>(dataset: &Dataset, mut data: T, memory_space: &Dataspace,
+                             file_space: &Dataspace) -> Result<()> {
+
+    try!(check(&data, memory_space, file_space));
+    ok!(ffi::H5Dread(dataset.id, data.datatype().id(), memory_space.id(), file_space.id(),
+                     ffi::H5P_DEFAULT, data.as_bytes_mut().as_mut_ptr() as *mut _),
+        "failed to read the data");
+    Ok(())
+}
+
+pub fn write<T: Data>(dataset: &Dataset, data: T, memory_space: &Dataspace,
+                      file_space: &Dataspace) -> Result<()> {
+
+    try!(check(&data, memory_space, file_space));
+    ok!(ffi::H5Dwrite(dataset.id, data.datatype().id(), memory_space.id(), file_space.id(),
+                      ffi::H5P_DEFAULT, data.as_bytes().as_ptr() as *const _),
+        "failed to write the data");
+    Ok(())
+}
+
+fn check<T: Data>(data: &T, memory_space: &Dataspace, file_space: &Dataspace) -> Result<()> {
+    let count = product!(data.dimensions());
+    if data.as_bytes().len() != count * try!(data.datatype().size()) {
+        raise!("the data should have the claimed number of elements");
+    }
+    if product!(try!(memory_space.dimensions())) != count {
+        raise!("the memory dataspace should have as many elements as the data");
+    }
+    if try!(memory_space.selection_size()) != try!(file_space.selection_size()) {
+        raise!("the memory and file selections should have the same number of elements");
+    }
+    Ok(())
+}
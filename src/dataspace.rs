@@ -3,19 +3,201 @@ use libc;
 
 use {ID, Result};
 
+/// A dataspace.
+///
+/// Dataspaces describe the dimensions of datasets and the elements selected
+/// for reading and writing.
 pub struct Dataspace {
     id: ID,
 }
 
+/// A hyperslab.
+///
+/// A hyperslab consists of `count` blocks whose starting positions are
+/// `stride` elements apart, beginning at `start`. By default, the stride and
+/// the block size are one along each dimension.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hyperslab {
+    start: Vec<usize>,
+    stride: Option<Vec<usize>>,
+    count: Vec<usize>,
+    block: Option<Vec<usize>>,
+}
+
+/// An operator combining a selection with the current one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operator {
+    /// Replace the current selection.
+    Set,
+    /// Select the union.
+    Or,
+    /// Select the intersection.
+    And,
+    /// Select the elements in either selection but not in both.
+    Xor,
+    /// Select the elements in the current selection but not in the new one.
+    NotB,
+    /// Select the elements in the new selection but not in the current one.
+    NotA,
+}
+
 identity!(Dataspace);
 
 impl Dataspace {
+    /// Create a dataspace with given dimensions.
+    ///
+    /// All the elements are selected. A dataspace without dimensions is a
+    /// scalar one, which contains exactly one element. Dimensions without
+    /// elements result in a simple dataspace of size zero.
+    #[inline]
+    pub fn new(dimensions: &[usize]) -> Result<Dataspace> {
+        new(dimensions)
+    }
+
+    /// Create a null dataspace.
+    ///
+    /// A null dataspace contains no elements. Its dimensions are reported as
+    /// one dimension of size zero.
+    #[inline]
+    pub fn new_null() -> Result<Dataspace> {
+        new_null()
+    }
+
+    /// Return the dimensions.
+    ///
+    /// A scalar dataspace has no dimensions, and a null one has one dimension
+    /// of size zero.
+    pub fn dimensions(&self) -> Result<Vec<usize>> {
+        Ok(try!(self.extent()).0)
+    }
+
+    /// Return the maximal dimensions.
+    ///
+    /// Unlimited dimensions are reported as `None`.
+    pub fn max_dimensions(&self) -> Result<Vec<Option<usize>>> {
+        Ok(try!(self.extent()).1)
+    }
+
+    /// Check if the dataspace is a null one.
+    pub fn is_null(&self) -> Result<bool> {
+        Ok(try!(self.class()) == ffi::H5S_NULL)
+    }
+
+    /// Check if the dataspace is a scalar one.
+    pub fn is_scalar(&self) -> Result<bool> {
+        Ok(try!(self.class()) == ffi::H5S_SCALAR)
+    }
+
+    /// Select a contiguous block of elements.
+    ///
+    /// The block replaces the current selection.
     pub fn select(&self, position: &[usize], size: &[usize]) -> Result<()> {
-        ok!(ffi::H5Sselect_hyperslab(self.id, ffi::H5S_SELECT_SET, position.as_ptr() as *const _,
-                                     0 as *const _, size.as_ptr() as *const _, 0 as *const _),
-            "failed to select the hyperslab region");
+        self.select_hyperslab(Operator::Set, &Hyperslab::new(position, size))
+    }
+
+    /// Select all the elements.
+    pub fn select_all(&self) -> Result<()> {
+        ok!(ffi::H5Sselect_all(self.id), "failed to select all the elements");
+        Ok(())
+    }
+
+    /// Combine a hyperslab with the current selection.
+    pub fn select_hyperslab(&self, operator: Operator, hyperslab: &Hyperslab) -> Result<()> {
+        let rank = try!(self.rank());
+        let check = |values: &[usize], name: &str| -> Result<Vec<ffi::hsize_t>> {
+            if values.len() != rank {
+                raise!("the {} should have the same number of dimensions as the dataspace", name);
+            }
+            Ok(values.iter().map(|&value| value as ffi::hsize_t).collect())
+        };
+        let start = try!(check(&hyperslab.start, "start"));
+        let count = try!(check(&hyperslab.count, "count"));
+        let stride = match hyperslab.stride {
+            Some(ref stride) => try!(check(stride, "stride")),
+            _ => vec![1; rank],
+        };
+        let block = match hyperslab.block {
+            Some(ref block) => try!(check(block, "block")),
+            _ => vec![1; rank],
+        };
+        ok!(ffi::H5Sselect_hyperslab(self.id, operator.into_raw(), start.as_ptr(), stride.as_ptr(),
+                                     count.as_ptr(), block.as_ptr()),
+            "failed to select a hyperslab");
+        Ok(())
+    }
+
+    /// Deselect all the elements.
+    pub fn select_none(&self) -> Result<()> {
+        ok!(ffi::H5Sselect_none(self.id), "failed to deselect the elements");
+        Ok(())
+    }
+
+    /// Select individual elements.
+    ///
+    /// The points replace the current selection, and the elements are read
+    /// and written in the order of the points.
+    pub fn select_points<T: AsRef<[usize]>>(&self, points: &[T]) -> Result<()> {
+        let rank = try!(self.rank());
+        let mut coordinates = Vec::with_capacity(rank * points.len());
+        for point in points {
+            let point = point.as_ref();
+            if point.len() != rank {
+                raise!("the points should have the same number of dimensions as the dataspace");
+            }
+            coordinates.extend(point.iter().map(|&value| value as ffi::hsize_t));
+        }
+        ok!(ffi::H5Sselect_elements(self.id, ffi::H5S_SELECT_SET, points.len() as libc::size_t,
+                                    coordinates.as_ptr()),
+            "failed to select the elements");
         Ok(())
     }
+
+    /// Return the first and the last positions of the bounding box of the
+    /// selection.
+    pub fn selection_bounds(&self) -> Result<(Vec<usize>, Vec<usize>)> {
+        let rank = try!(self.rank());
+        let mut start = vec![0 as ffi::hsize_t; rank];
+        let mut end = vec![0 as ffi::hsize_t; rank];
+        ok!(ffi::H5Sget_select_bounds(self.id, start.as_mut_ptr(), end.as_mut_ptr()),
+            "failed to obtain the bounds of the selection");
+        Ok((start.into_iter().map(|value| value as usize).collect(),
+            end.into_iter().map(|value| value as usize).collect()))
+    }
+
+    /// Return the number of selected elements.
+    pub fn selection_size(&self) -> Result<usize> {
+        Ok(ok!(ffi::H5Sget_select_npoints(self.id),
+               "failed to obtain the number of selected elements") as usize)
+    }
+
+    fn class(&self) -> Result<ffi::H5S_class_t> {
+        match unsafe { ffi::H5Sget_simple_extent_type(self.id) } {
+            ffi::H5S_NO_CLASS => raise!("failed to obtain the class"),
+            class => Ok(class),
+        }
+    }
+
+    fn extent(&self) -> Result<(Vec<usize>, Vec<Option<usize>>)> {
+        if try!(self.is_null()) {
+            return Ok((vec![0], vec![Some(0)]));
+        }
+        let count = try!(self.rank());
+        let mut dimensions = vec![0 as ffi::hsize_t; count];
+        let mut max_dimensions = vec![0 as ffi::hsize_t; count];
+        ok!(ffi::H5Sget_simple_extent_dims(self.id, dimensions.as_mut_ptr(),
+                                           max_dimensions.as_mut_ptr()),
+            "failed to obtain the dimensions");
+        let dimensions = dimensions.into_iter().map(|dimension| dimension as usize).collect();
+        let max_dimensions = max_dimensions.into_iter().map(|dimension| {
+            if dimension == ffi::H5S_UNLIMITED { None } else { Some(dimension as usize) }
+        }).collect();
+        Ok((dimensions, max_dimensions))
+    }
+
+    fn rank(&self) -> Result<usize> {
+        Ok(ok!(ffi::H5Sget_simple_extent_ndims(self.id),
+               "failed to obtain the number of dimensions") as usize)
+    }
 }
 
 impl Drop for Dataspace {
@@ -24,14 +206,91 @@ impl Drop for Dataspace {
     }
 }
 
+impl Hyperslab {
+    /// Create a hyperslab of `count` elements beginning at `start`.
+    pub fn new(start: &[usize], count: &[usize]) -> Hyperslab {
+        Hyperslab { start: start.to_vec(), stride: None, count: count.to_vec(), block: None }
+    }
+
+    /// Return the dimensions of the selected elements taken together.
+    pub fn dimensions(&self) -> Vec<usize> {
+        let block = self.block.clone().unwrap_or_else(|| vec![1; self.count.len()]);
+        self.count.iter().zip(&block).map(|(&count, &block)| count * block).collect()
+    }
+
+    /// Set the distance between the starting positions of the blocks.
+    pub fn stride(mut self, stride: &[usize]) -> Hyperslab {
+        self.stride = Some(stride.to_vec());
+        self
+    }
+
+    /// Set the size of the blocks.
+    pub fn block(mut self, block: &[usize]) -> Hyperslab {
+        self.block = Some(block.to_vec());
+        self
+    }
+}
+
+impl Operator {
+    fn into_raw(self) -> ffi::H5S_seloper_t {
+        match self {
+            Operator::Set => ffi::H5S_SELECT_SET,
+            Operator::Or => ffi::H5S_SELECT_OR,
+            Operator::And => ffi::H5S_SELECT_AND,
+            Operator::Xor => ffi::H5S_SELECT_XOR,
+            Operator::NotB => ffi::H5S_SELECT_NOTB,
+            Operator::NotA => ffi::H5S_SELECT_NOTA,
+        }
+    }
+}
+
 pub fn new(dimensions: &[usize]) -> Result<Dataspace> {
+    if dimensions.is_empty() {
+        return new_scalar();
+    }
+    let dimensions = dimensions.iter().map(|&dimension| dimension as ffi::hsize_t)
+                                      .collect::<Vec<_>>();
     Ok(Dataspace {
-        id: ok!(ffi::H5Screate_simple(dimensions.len() as libc::c_int,
-                                      dimensions.as_ptr() as *const _, 0 as *const _),
+        id: ok!(ffi::H5Screate_simple(dimensions.len() as libc::c_int, dimensions.as_ptr(),
+                                      0 as *const _),
                 "failed to create a dataspace"),
     })
 }
 
+pub fn new_extendible(dimensions: &[usize], max_dimensions: &[Option<usize>])
+                      -> Result<Dataspace> {
+
+    if dimensions.len() != max_dimensions.len() {
+        raise!("the maximal dimensions should have the same number of dimensions");
+    }
+    if dimensions.is_empty() {
+        return new_scalar();
+    }
+    let dimensions = dimensions.iter().map(|&dimension| dimension as ffi::hsize_t)
+                                      .collect::<Vec<_>>();
+    let max_dimensions = max_dimensions.iter().map(|dimension| match *dimension {
+        Some(dimension) => dimension as ffi::hsize_t,
+        _ => ffi::H5S_UNLIMITED,
+    }).collect::<Vec<_>>();
+    Ok(Dataspace {
+        id: ok!(ffi::H5Screate_simple(dimensions.len() as libc::c_int, dimensions.as_ptr(),
+                                      max_dimensions.as_ptr()),
+                "failed to create a dataspace"),
+    })
+}
+
+pub fn new_null() -> Result<Dataspace> {
+    Ok(Dataspace {
+        id: ok!(ffi::H5Screate(ffi::H5S_NULL), "failed to create a null dataspace"),
+    })
+}
+
+pub fn new_scalar() -> Result<Dataspace> {
+    Ok(Dataspace {
+        id: ok!(ffi::H5Screate(ffi::H5S_SCALAR), "failed to create a scalar dataspace"),
+    })
+}
+
 pub fn from_raw(id: ID) -> Dataspace {
     Dataspace { id: id }
 }
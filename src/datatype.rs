@@ -1,13 +1,69 @@
 use ffi;
 use libc;
+use std::ffi::CStr;
 use std::rc::Rc;
+#[cfg(any(feature = "serde", feature = "serialize"))]
+use std::{cmp, mem};
 
 use {ID, Identity, Result};
 
-#[doc(hidden)]
+/// A datatype.
 #[derive(Clone)]
 pub struct Datatype(Rc<Inner>);
 
+/// A class of datatypes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Class {
+    /// Fixed-size arrays.
+    Array,
+    /// Bit fields.
+    Bitfield,
+    /// Compounds of named members.
+    Compound,
+    /// Enums.
+    Enum,
+    /// Floating-point numbers.
+    Float,
+    /// Integers.
+    Integer,
+    /// Opaque data.
+    Opaque,
+    /// References to objects and regions.
+    Reference,
+    /// Strings.
+    String,
+    /// Dates and times.
+    Time,
+    /// Variable-length sequences.
+    Vlen,
+}
+
+/// A member of a compound datatype.
+#[derive(Clone)]
+pub struct Member {
+    /// The name.
+    pub name: String,
+    /// The offset in bytes from the beginning of the compound.
+    pub offset: usize,
+    /// The datatype.
+    pub datatype: Datatype,
+}
+
+/// A byte order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Order {
+    /// The most significant byte comes first.
+    BigEndian,
+    /// The least significant byte comes first.
+    LittleEndian,
+    /// The members of a compound have different orders.
+    Mixed,
+    /// The order is not applicable, as for strings.
+    None,
+    /// The order of VAX machines.
+    Vax,
+}
+
 struct Inner {
     id: ID,
     owned: bool,
@@ -20,6 +76,168 @@ macro_rules! new(
 );
 
 impl Datatype {
+    /// Create an array datatype with given dimensions.
+    #[inline]
+    pub fn new_array(base: &Datatype, dimensions: &[usize]) -> Result<Datatype> {
+        new_array(base, dimensions)
+    }
+
+    /// Create a compound datatype of a particular size with given members.
+    pub fn new_compound(size: usize, members: &[Member]) -> Result<Datatype> {
+        let id = ok!(ffi::H5Tcreate(ffi::H5T_COMPOUND, size as libc::size_t),
+                     "failed to create a compound datatype");
+        let datatype = new!(id, true);
+        for member in members {
+            ok!(ffi::H5Tinsert(id, str_to_cstr!(&member.name[..]).as_ptr(),
+                               member.offset as libc::size_t, member.datatype.id()),
+                "failed to insert a member {:?}", member.name);
+        }
+        Ok(datatype)
+    }
+
+    /// Create a native floating-point datatype of a particular size in bytes.
+    pub fn new_float(size: usize) -> Result<Datatype> {
+        let id = match size {
+            4 => unsafe { ffi::H5T_NATIVE_FLOAT },
+            8 => unsafe { ffi::H5T_NATIVE_DOUBLE },
+            _ => raise!("found an unsupported size of floating-point numbers {}", size),
+        };
+        Ok(new!(ok!(ffi::H5Tcopy(id), "failed to create a floating-point datatype"), true))
+    }
+
+    /// Create a native integer datatype of a particular size in bytes.
+    pub fn new_integer(size: usize, signed: bool) -> Result<Datatype> {
+        let id = unsafe {
+            match (size, signed) {
+                (1, true) => ffi::H5T_NATIVE_INT8,
+                (1, false) => ffi::H5T_NATIVE_UINT8,
+                (2, true) => ffi::H5T_NATIVE_INT16,
+                (2, false) => ffi::H5T_NATIVE_UINT16,
+                (4, true) => ffi::H5T_NATIVE_INT32,
+                (4, false) => ffi::H5T_NATIVE_UINT32,
+                (8, true) => ffi::H5T_NATIVE_INT64,
+                (8, false) => ffi::H5T_NATIVE_UINT64,
+                _ => raise!("found an unsupported size of integers {}", size),
+            }
+        };
+        Ok(new!(ok!(ffi::H5Tcopy(id), "failed to create an integer datatype"), true))
+    }
+
+    /// Create a fixed-length string datatype.
+    #[inline]
+    pub fn new_string(length: usize) -> Result<Datatype> {
+        new_string(length)
+    }
+
+    /// Create a variable-length string datatype.
+    #[inline]
+    pub fn new_variable_string() -> Result<Datatype> {
+        new_variable_string()
+    }
+
+    /// Create a variable-length datatype.
+    #[inline]
+    pub fn new_vlen(base: &Datatype) -> Result<Datatype> {
+        new_vlen(base)
+    }
+
+    /// Return the base datatype of an array, enum, or variable-length
+    /// datatype.
+    pub fn base(&self) -> Result<Datatype> {
+        Ok(new!(ok!(ffi::H5Tget_super(self.0.id), "failed to obtain the base datatype"), true))
+    }
+
+    /// Return the class.
+    pub fn class(&self) -> Result<Class> {
+        Ok(match unsafe { ffi::H5Tget_class(self.0.id) } {
+            ffi::H5T_ARRAY => Class::Array,
+            ffi::H5T_BITFIELD => Class::Bitfield,
+            ffi::H5T_COMPOUND => Class::Compound,
+            ffi::H5T_ENUM => Class::Enum,
+            ffi::H5T_FLOAT => Class::Float,
+            ffi::H5T_INTEGER => Class::Integer,
+            ffi::H5T_OPAQUE => Class::Opaque,
+            ffi::H5T_REFERENCE => Class::Reference,
+            ffi::H5T_STRING => Class::String,
+            ffi::H5T_TIME => Class::Time,
+            ffi::H5T_VLEN => Class::Vlen,
+            _ => raise!("failed to obtain the class"),
+        })
+    }
+
+    /// Return the dimensions of an array datatype.
+    pub fn dimensions(&self) -> Result<Vec<usize>> {
+        let rank = ok!(ffi::H5Tget_array_ndims(self.0.id),
+                       "failed to obtain the number of dimensions");
+        let mut dimensions = vec![0; rank as usize];
+        ok!(ffi::H5Tget_array_dims2(self.0.id, dimensions.as_mut_ptr()),
+            "failed to obtain the dimensions");
+        Ok(dimensions.iter().map(|&dimension| dimension as usize).collect())
+    }
+
+    /// Check if an integer datatype is signed.
+    pub fn is_signed(&self) -> Result<bool> {
+        match unsafe { ffi::H5Tget_sign(self.0.id) } {
+            ffi::H5T_SGN_NONE => Ok(false),
+            ffi::H5T_SGN_2 => Ok(true),
+            _ => raise!("failed to obtain the sign"),
+        }
+    }
+
+    /// Check if the datatype is a variable-length string.
+    #[inline]
+    pub fn is_variable_string(&self) -> bool {
+        is_variable_string(self)
+    }
+
+    /// Return the members of a compound datatype.
+    pub fn members(&self) -> Result<Vec<Member>> {
+        let count = ok!(ffi::H5Tget_nmembers(self.0.id),
+                        "failed to obtain the number of members") as libc::c_uint;
+        let mut members = Vec::with_capacity(count as usize);
+        for i in 0..count {
+            let name = try!(member_name(self, i));
+            let offset = unsafe { ffi::H5Tget_member_offset(self.0.id, i) } as usize;
+            let datatype = ok!(ffi::H5Tget_member_type(self.0.id, i),
+                               "failed to obtain the datatype of a member {:?}", name);
+            members.push(Member { name: name, offset: offset, datatype: new!(datatype, true) });
+        }
+        Ok(members)
+    }
+
+    /// Return a copy of the datatype with the padding in compounds removed.
+    ///
+    /// Datatypes containing no compounds are copied as they are.
+    pub fn packed(&self) -> Result<Datatype> {
+        let datatype = new!(ok!(ffi::H5Tcopy(self.0.id), "failed to copy a datatype"), true);
+        if ok!(ffi::H5Tdetect_class(self.0.id, ffi::H5T_COMPOUND),
+               "failed to detect compounds in a datatype") > 0 {
+            ok!(ffi::H5Tpack(datatype.id()), "failed to pack a datatype");
+        }
+        Ok(datatype)
+    }
+
+    /// Return the byte order.
+    pub fn order(&self) -> Result<Order> {
+        Ok(match unsafe { ffi::H5Tget_order(self.0.id) } {
+            ffi::H5T_ORDER_BE => Order::BigEndian,
+            ffi::H5T_ORDER_LE => Order::LittleEndian,
+            ffi::H5T_ORDER_MIXED => Order::Mixed,
+            ffi::H5T_ORDER_NONE => Order::None,
+            ffi::H5T_ORDER_VAX => Order::Vax,
+            _ => raise!("failed to obtain the byte order"),
+        })
+    }
+
+    /// Return the number of significant bits.
+    pub fn precision(&self) -> Result<usize> {
+        let precision = unsafe { ffi::H5Tget_precision(self.0.id) };
+        if precision <= 0 {
+            raise!("failed to obtain the precision");
+        }
+        Ok(precision as usize)
+    }
+
     /// Return the size in bytes.
     pub fn size(&self) -> Result<usize> {
         let size = unsafe { ffi::H5Tget_size(self.0.id) };
@@ -28,6 +246,16 @@ impl Datatype {
         }
         Ok(size as usize)
     }
+
+    /// Return the names of the variants of an enum datatype.
+    pub fn variants(&self) -> Result<Vec<String>> {
+        if try!(self.class()) != Class::Enum {
+            raise!("the datatype should be an enum");
+        }
+        let count = ok!(ffi::H5Tget_nmembers(self.0.id),
+                        "failed to obtain the number of variants") as libc::c_uint;
+        (0..count).map(|i| member_name(self, i)).collect()
+    }
 }
 
 impl Identity for Datatype {
@@ -52,22 +280,140 @@ impl PartialEq for Datatype {
     }
 }
 
+/// Compute the alignment of a datatype in memory.
+///
+/// Atomic datatypes are aligned as the native types of the same size, and
+/// compounds as their most aligned members.
+#[cfg(any(feature = "serde", feature = "serialize"))]
+pub fn alignment(datatype: &Datatype) -> Result<usize> {
+    Ok(match try!(datatype.class()) {
+        Class::Array | Class::Enum => try!(alignment(&try!(datatype.base()))),
+        Class::Compound => {
+            let mut largest = 1;
+            for member in try!(datatype.members()) {
+                largest = cmp::max(largest, try!(alignment(&member.datatype)));
+            }
+            largest
+        },
+        Class::Opaque => 1,
+        Class::String if is_variable_string(datatype) => mem::align_of::<*const libc::c_char>(),
+        Class::String => 1,
+        Class::Vlen => mem::align_of::<ffi::hvl_t>(),
+        _ => match try!(datatype.size()) {
+            1 => mem::align_of::<u8>(),
+            2 => mem::align_of::<u16>(),
+            4 => mem::align_of::<u32>(),
+            _ => mem::align_of::<u64>(),
+        },
+    })
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+pub fn enum_name(datatype: &Datatype, value: &[u8]) -> Result<String> {
+    let mut buffer = vec![0u8; 256];
+    ok!(ffi::H5Tenum_nameof(datatype.id(), value.as_ptr() as *const _,
+                            buffer.as_mut_ptr() as *mut _, buffer.len() as libc::size_t),
+        "failed to obtain the name of an enum value");
+    let length = buffer.iter().position(|&byte| byte == 0).unwrap_or(buffer.len());
+    buffer.truncate(length);
+    match String::from_utf8(buffer) {
+        Ok(name) => Ok(name),
+        _ => raise!("failed to process the name of an enum value"),
+    }
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+pub fn find_member(datatype: &Datatype, name: &str) -> Result<Option<Member>> {
+    Ok(try!(datatype.members()).into_iter().find(|member| member.name == name))
+}
+
+#[inline]
+pub fn from_raw(id: ID) -> Datatype {
+    new!(id, true)
+}
+
 #[inline]
 pub fn from_raw_borrowed(id: ID) -> Datatype {
     new!(id, false)
 }
 
-#[cfg(feature = "serialize")]
-pub fn new_compound(fields: &[(String, Datatype, usize)]) -> Result<Datatype> {
-    let size = fields.iter().fold(0, |sum, &(_, _, size)| sum + size) as libc::size_t;
-    let id = ok!(ffi::H5Tcreate(ffi::H5T_COMPOUND, size), "failed to create a compound datatype");
-    let mut offset = 0;
-    for &(ref name, ref datatype, size) in fields.iter() {
-        ok!(ffi::H5Tinsert(id, str_to_cstr!(&name[..]).as_ptr(), offset as libc::size_t,
-                           datatype.id()));
-        offset += size;
+#[inline]
+pub fn is_array(datatype: &Datatype) -> bool {
+    unsafe { ffi::H5Tget_class(datatype.id()) == ffi::H5T_ARRAY }
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+#[inline]
+pub fn is_compound(datatype: &Datatype) -> bool {
+    unsafe { ffi::H5Tget_class(datatype.id()) == ffi::H5T_COMPOUND }
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+#[inline]
+pub fn is_enum(datatype: &Datatype) -> bool {
+    unsafe { ffi::H5Tget_class(datatype.id()) == ffi::H5T_ENUM }
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+#[inline]
+pub fn is_placeholder(datatype: &Datatype) -> bool {
+    unsafe { ffi::H5Tget_class(datatype.id()) == ffi::H5T_OPAQUE }
+}
+
+#[inline]
+pub fn is_string(datatype: &Datatype) -> bool {
+    unsafe { ffi::H5Tget_class(datatype.id()) == ffi::H5T_STRING }
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+#[inline]
+pub fn is_vlen(datatype: &Datatype) -> bool {
+    unsafe { ffi::H5Tget_class(datatype.id()) == ffi::H5T_VLEN }
+}
+
+#[inline]
+pub fn is_variable_string(datatype: &Datatype) -> bool {
+    unsafe { ffi::H5Tis_variable_str(datatype.id()) > 0 }
+}
+
+pub fn native(datatype: &Datatype) -> Result<Datatype> {
+    Ok(new!(ok!(ffi::H5Tget_native_type(datatype.id(), ffi::H5T_DIR_ASCEND),
+                "failed to obtain the native datatype"), true))
+}
+
+pub fn new_array(base: &Datatype, dimensions: &[usize]) -> Result<Datatype> {
+    if dimensions.is_empty() || dimensions.iter().any(|&dimension| dimension == 0) {
+        raise!("array datatypes should have nonzero dimensions");
     }
-    Ok(new!(id, true))
+    let dimensions = dimensions.iter().map(|&dimension| dimension as ffi::hsize_t)
+                                      .collect::<Vec<_>>();
+    Ok(new!(ok!(ffi::H5Tarray_create2(base.id(), dimensions.len() as libc::c_uint,
+                                      dimensions.as_ptr()),
+                "failed to create an array datatype"), true))
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+pub fn new_enum(variants: &[(String, u32)]) -> Result<Datatype> {
+    let id = ok!(ffi::H5Tenum_create(ffi::H5T_NATIVE_UINT32), "failed to create an enum datatype");
+    let datatype = new!(id, true);
+    for &(ref name, value) in variants.iter() {
+        ok!(ffi::H5Tenum_insert(id, str_to_cstr!(&name[..]).as_ptr(),
+                                &value as *const _ as *const _),
+            "failed to insert an enum variant {:?}", name);
+    }
+    Ok(datatype)
+}
+
+/// Create a datatype standing for the elements of empty arrays.
+///
+/// The datatype is replaced with the actual one when unified with it.
+#[cfg(any(feature = "serde", feature = "serialize"))]
+pub fn new_placeholder() -> Result<Datatype> {
+    let id = ok!(ffi::H5Tcreate(ffi::H5T_OPAQUE, 1), "failed to create an opaque datatype");
+    let datatype = new!(id, true);
+    ok!(ffi::H5Tset_tag(id, str_to_cstr!("empty").as_ptr()),
+        "failed to set the tag of an opaque datatype");
+    Ok(datatype)
 }
 
 pub fn new_string(length: usize) -> Result<Datatype> {
@@ -77,3 +423,120 @@ pub fn new_string(length: usize) -> Result<Datatype> {
     ok!(ffi::H5Tset_cset(id, ffi::H5T_CSET_UTF8));
     Ok(new!(id, true))
 }
+
+pub fn new_variable_string() -> Result<Datatype> {
+    let id = ok!(ffi::H5Tcopy(ffi::H5T_C_S1), "failed to create a string datatype");
+    let datatype = new!(id, true);
+    ok!(ffi::H5Tset_size(id, ffi::H5T_VARIABLE),
+        "failed to set the size of a string datatype");
+    ok!(ffi::H5Tset_cset(id, ffi::H5T_CSET_UTF8));
+    Ok(datatype)
+}
+
+pub fn new_vlen(base: &Datatype) -> Result<Datatype> {
+    Ok(new!(ok!(ffi::H5Tvlen_create(base.id()), "failed to create a variable-length datatype"),
+            true))
+}
+
+/// Find a datatype compatible with two given ones.
+///
+/// Two enum datatypes are merged if their variants do not conflict, two
+/// compound datatypes are merged field by field if their layouts coincide, and
+/// two array or variable-length datatypes are merged if their base datatypes
+/// are and their dimensions, if any, coincide.
+#[cfg(any(feature = "serde", feature = "serialize"))]
+pub fn unify(one: &Datatype, other: &Datatype) -> Result<Option<Datatype>> {
+    if one == other {
+        return Ok(Some(one.clone()));
+    }
+    if is_enum(one) && is_enum(other) {
+        let mut merged = try!(variants(one));
+        for (name, value) in try!(variants(other)) {
+            match merged.iter().find(|variant| variant.0 == name).map(|variant| variant.1) {
+                Some(known) if known != value => return Ok(None),
+                Some(_) => {},
+                _ => merged.push((name, value)),
+            }
+        }
+        if merged.iter().any(|one| merged.iter().any(|other| one.1 == other.1 &&
+                                                             one.0 != other.0)) {
+            return Ok(None);
+        }
+        merged.sort_by_key(|variant| variant.1);
+        return Ok(Some(try!(new_enum(&merged))));
+    }
+    if is_array(one) && is_array(other) {
+        let dimensions = try!(one.dimensions());
+        if dimensions != try!(other.dimensions()) {
+            return Ok(None);
+        }
+        return Ok(match try!(unify(&try!(one.base()), &try!(other.base()))) {
+            Some(base) => Some(try!(new_array(&base, &dimensions))),
+            _ => None,
+        });
+    }
+    if is_vlen(one) && is_vlen(other) {
+        let (one, other) = (try!(one.base()), try!(other.base()));
+        let base = if is_placeholder(&one) {
+            other
+        } else if is_placeholder(&other) {
+            one
+        } else {
+            match try!(unify(&one, &other)) {
+                Some(base) => base,
+                _ => return Ok(None),
+            }
+        };
+        return Ok(Some(try!(new_vlen(&base))));
+    }
+    if is_compound(one) && is_compound(other) {
+        let size = try!(one.size());
+        if size != try!(other.size()) {
+            return Ok(None);
+        }
+        let (one, other) = (try!(one.members()), try!(other.members()));
+        if one.len() != other.len() {
+            return Ok(None);
+        }
+        let mut members = Vec::with_capacity(one.len());
+        for (one, other) in one.into_iter().zip(other.into_iter()) {
+            if one.name != other.name || one.offset != other.offset {
+                return Ok(None);
+            }
+            let datatype = match try!(unify(&one.datatype, &other.datatype)) {
+                Some(datatype) => datatype,
+                _ => return Ok(None),
+            };
+            members.push(Member { datatype: datatype, ..one });
+        }
+        return Ok(Some(try!(Datatype::new_compound(size, &members))));
+    }
+    Ok(None)
+}
+
+fn member_name(datatype: &Datatype, i: libc::c_uint) -> Result<String> {
+    let pointer = unsafe { ffi::H5Tget_member_name(datatype.id(), i) };
+    if pointer.is_null() {
+        raise!("failed to obtain the name of a member");
+    }
+    let name = unsafe { CStr::from_ptr(pointer).to_str().map(|name| name.to_string()) };
+    unsafe { libc::free(pointer as *mut _) };
+    match name {
+        Ok(name) => Ok(name),
+        _ => raise!("failed to process the name of a member"),
+    }
+}
+
+#[cfg(any(feature = "serde", feature = "serialize"))]
+fn variants(datatype: &Datatype) -> Result<Vec<(String, u32)>> {
+    let count = ok!(ffi::H5Tget_nmembers(datatype.id()),
+                    "failed to obtain the number of variants") as libc::c_uint;
+    let mut variants = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        let mut value = 0u32;
+        ok!(ffi::H5Tget_member_value(datatype.id(), i, &mut value as *mut _ as *mut _),
+            "failed to obtain the value of a variant");
+        variants.push((try!(member_name(datatype, i)), value));
+    }
+    Ok(variants)
+}
@@ -1,181 +1,327 @@
 use rustc_serialize;
+use std::mem;
 
-use {Error, Result};
+use compound;
+use data::{Buffer, Data, FromData};
+use dataset::{self, Dataset};
+use datatype;
+use link::Link;
+use {Error, Location, Result};
 
 /// A decoder.
-#[doc(hidden)]
-pub struct Decoder;
+///
+/// Decoders are suitable for loading structural data.
+pub struct Decoder<'l> {
+    location: &'l Location,
+    name: Option<String>,
+    state: State,
+}
+
+enum State {
+    Uncertain,
+    Sequence(Sequence),
+    Structure(Structure),
+}
+
+struct Sequence {
+    buffer: Buffer,
+    position: usize,
+}
+
+struct Structure {
+    buffer: Buffer,
+}
+
+impl<'l> Decoder<'l> {
+    /// Create a decoder.
+    pub fn new(location: &'l Location, name: &str) -> Decoder<'l> {
+        Decoder { location: location, name: Some(name.to_string()), state: State::Uncertain }
+    }
+
+    fn element(&mut self) -> Result<Buffer> {
+        match self.state {
+            State::Uncertain => match self.name.take() {
+                Some(ref name) => {
+                    let dataset = try!(Dataset::open(self.location, name));
+                    dataset::read(&dataset, &try!(datatype::native(&try!(dataset.datatype()))))
+                },
+                _ => raise!("cannot read data without a name"),
+            },
+            State::Sequence(ref mut sequence) => {
+                sequence.position += 1;
+                compound::element(&sequence.buffer, sequence.position - 1)
+            },
+            State::Structure(ref structure) => match self.name.take() {
+                Some(name) => match try!(compound::field(&structure.buffer, &name)) {
+                    Some(buffer) => Ok(buffer),
+                    _ => raise!("found no field {:?}", name),
+                },
+                _ => raise!("cannot read a field without a name"),
+            },
+        }
+    }
+
+    fn exists(&self) -> Result<bool> {
+        match self.state {
+            State::Uncertain => match self.name {
+                Some(ref name) => Link::exists(self.location, name),
+                _ => raise!("cannot read data without a name"),
+            },
+            State::Sequence(_) => Ok(true),
+            State::Structure(ref structure) => match self.name {
+                Some(ref name) => Ok(try!(compound::field(&structure.buffer, name)).is_some()),
+                _ => raise!("cannot read a field without a name"),
+            },
+        }
+    }
+
+    fn sequence<T, F>(&mut self, next: F) -> Result<T>
+        where F: FnOnce(&mut Self, usize) -> Result<T>
+    {
+        let buffer = match self.state {
+            State::Uncertain => try!(self.element()),
+            _ => try!(compound::expand(try!(self.element()))),
+        };
+        let length = product!(buffer.dimensions());
+        let sequence = Sequence { buffer: buffer, position: 0 };
+        let state = mem::replace(&mut self.state, State::Sequence(sequence));
+        let result = next(self, length);
+        self.state = state;
+        result
+    }
+
+    fn structure<T, F>(&mut self, next: F) -> Result<T> where F: FnOnce(&mut Self) -> Result<T> {
+        let buffer = try!(self.element());
+        if !datatype::is_compound(&buffer.datatype()) {
+            raise!("the data should have a compound datatype");
+        }
+        if product!(buffer.dimensions()) != 1 {
+            raise!("the data should contain exactly one structure");
+        }
+        let state = mem::replace(&mut self.state, State::Structure(Structure { buffer: buffer }));
+        let result = next(self);
+        self.state = state;
+        result
+    }
+
+    fn structure_field<T, F>(&mut self, name: &str, next: F) -> Result<T>
+        where F: FnOnce(&mut Self) -> Result<T>
+    {
+        let name = mem::replace(&mut self.name, Some(name.to_string()));
+        let result = next(self);
+        self.name = name;
+        result
+    }
+
+    fn tuple<T, F>(&mut self, count: usize, next: F) -> Result<T>
+        where F: FnOnce(&mut Self) -> Result<T>
+    {
+        self.sequence(|decoder, length| {
+            if length != count {
+                raise!("the data should contain exactly {} elements", count);
+            }
+            next(decoder)
+        })
+    }
+}
 
-impl rustc_serialize::Decoder for Decoder {
+impl<'l> rustc_serialize::Decoder for Decoder<'l> {
     type Error = Error;
 
-    fn error(&mut self, _: &str) -> Error {
-        unimplemented!();
+    #[inline]
+    fn error(&mut self, message: &str) -> Error {
+        Error(message.to_string())
     }
 
+    #[inline]
     fn read_bool(&mut self) -> Result<bool> {
-        unimplemented!();
+        bool::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_char(&mut self) -> Result<char> {
-        unimplemented!();
+        char::from_data(try!(self.element()))
     }
 
-    fn read_enum<T, F>(&mut self, _: &str, _: F) -> Result<T>
+    #[inline]
+    fn read_enum<T, F>(&mut self, _: &str, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        unimplemented!();
+        next(self)
     }
 
     fn read_enum_struct_variant<T, F>(&mut self, _: &[&str], _: F) -> Result<T>
         where F: FnMut(&mut Self, usize) -> Result<T>
     {
-        panic!("HDF5 does not support enum structs");
+        raise!("HDF5 does not support enum structs");
     }
 
     fn read_enum_struct_variant_field<T, F>(&mut self, _: &str, _: usize, _: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support enum structs");
+        raise!("HDF5 does not support enum structs");
     }
 
-    fn read_enum_variant<T, F>(&mut self, _: &[&str], _: F) -> Result<T>
+    fn read_enum_variant<T, F>(&mut self, names: &[&str], mut next: F) -> Result<T>
         where F: FnMut(&mut Self, usize) -> Result<T>
     {
-        unimplemented!();
+        let id = try!(compound::variant_index(try!(self.element()), names));
+        next(self, id)
     }
 
     fn read_enum_variant_arg<T, F>(&mut self, _: usize, _: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support enums with arguments");
+        raise!("HDF5 does not support enums with arguments");
     }
 
+    #[inline]
     fn read_f64(&mut self) -> Result<f64> {
-        unimplemented!();
+        f64::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_f32(&mut self) -> Result<f32> {
-        unimplemented!();
+        f32::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_i8(&mut self) -> Result<i8> {
-        unimplemented!();
+        i8::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_i16(&mut self) -> Result<i16> {
-        unimplemented!();
+        i16::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_i32(&mut self) -> Result<i32> {
-        unimplemented!();
+        i32::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_i64(&mut self) -> Result<i64> {
-        unimplemented!();
+        i64::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_isize(&mut self) -> Result<isize> {
-        unimplemented!();
+        isize::from_data(try!(self.element()))
     }
 
     fn read_map<T, F>(&mut self, _: F) -> Result<T>
         where F: FnOnce(&mut Self, usize) -> Result<T>
     {
-        panic!("HDF5 does not support maps");
+        raise!("HDF5 does not support maps");
     }
 
     fn read_map_elt_key<T, F>(&mut self, _: usize, _: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support maps");
+        raise!("HDF5 does not support maps");
     }
 
     fn read_map_elt_val<T, F>(&mut self, _: usize, _: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support maps");
+        raise!("HDF5 does not support maps");
     }
 
     fn read_nil(&mut self) -> Result<()> {
-        panic!("HDF5 does not support nils");
+        raise!("HDF5 does not support nils");
     }
 
-    fn read_option<T, F>(&mut self, _: F) -> Result<T>
+    fn read_option<T, F>(&mut self, mut next: F) -> Result<T>
         where F: FnMut(&mut Self, bool) -> Result<T>
     {
-        panic!("HDF5 does not support options");
+        let exists = try!(self.exists());
+        next(self, exists)
     }
 
-    fn read_seq<T, F>(&mut self, _: F) -> Result<T>
+    #[inline]
+    fn read_seq<T, F>(&mut self, next: F) -> Result<T>
         where F: FnOnce(&mut Self, usize) -> Result<T>
     {
-        unimplemented!();
+        self.sequence(next)
     }
 
-    fn read_seq_elt<T, F>(&mut self, _: usize, _: F) -> Result<T>
+    #[inline]
+    fn read_seq_elt<T, F>(&mut self, _: usize, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        unimplemented!();
+        next(self)
     }
 
+    #[inline]
     fn read_str(&mut self) -> Result<String> {
-        unimplemented!();
+        String::from_data(try!(self.element()))
     }
 
-    fn read_struct<T, F>(&mut self, _: &str, _: usize, _: F) -> Result<T>
+    #[inline]
+    fn read_struct<T, F>(&mut self, _: &str, _: usize, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        unimplemented!();
+        self.structure(next)
     }
 
-    fn read_struct_field<T, F>(&mut self, _: &str, _: usize, _: F) -> Result<T>
+    #[inline]
+    fn read_struct_field<T, F>(&mut self, name: &str, _: usize, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        unimplemented!();
+        self.structure_field(name, next)
     }
 
-    fn read_tuple<T, F>(&mut self, _: usize, _: F) -> Result<T>
+    #[inline]
+    fn read_tuple<T, F>(&mut self, count: usize, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support tuples");
+        self.tuple(count, next)
     }
 
-    fn read_tuple_arg<T, F>(&mut self, _: usize, _: F) -> Result<T>
+    #[inline]
+    fn read_tuple_arg<T, F>(&mut self, _: usize, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support tuples");
+        next(self)
     }
 
-    fn read_tuple_struct<T, F>(&mut self, _: &str, _: usize, _: F) -> Result<T>
+    #[inline]
+    fn read_tuple_struct<T, F>(&mut self, _: &str, count: usize, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support tuples");
+        self.tuple(count, next)
     }
 
-    fn read_tuple_struct_arg<T, F>(&mut self, _: usize, _: F) -> Result<T>
+    #[inline]
+    fn read_tuple_struct_arg<T, F>(&mut self, _: usize, next: F) -> Result<T>
         where F: FnOnce(&mut Self) -> Result<T>
     {
-        panic!("HDF5 does not support tuples");
+        next(self)
     }
 
+    #[inline]
     fn read_u8(&mut self) -> Result<u8> {
-        unimplemented!();
+        u8::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_u16(&mut self) -> Result<u16> {
-        unimplemented!();
+        u16::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_u32(&mut self) -> Result<u32> {
-        unimplemented!();
+        u32::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_u64(&mut self) -> Result<u64> {
-        unimplemented!();
+        u64::from_data(try!(self.element()))
     }
 
+    #[inline]
     fn read_usize(&mut self) -> Result<usize> {
-        unimplemented!();
+        usize::from_data(try!(self.element()))
     }
 }
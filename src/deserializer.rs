@@ -0,0 +1,311 @@
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use std::fmt::Display;
+use std::mem;
+
+use compound;
+use data::{Buffer, Data, FromData};
+use dataset::{self, Dataset};
+use datatype;
+use link::Link;
+use {Error, Location, Result};
+
+/// A deserializer.
+///
+/// Deserializers are suitable for loading structural data.
+pub struct Deserializer<'l> {
+    location: &'l Location,
+    name: Option<String>,
+    state: State,
+}
+
+enum State {
+    Uncertain,
+    Sequence(Buffer, usize),
+    Structure(Buffer),
+}
+
+struct Elements<'s, 'l: 's> {
+    deserializer: &'s mut Deserializer<'l>,
+    remaining: usize,
+}
+
+struct Fields<'s, 'l: 's> {
+    deserializer: &'s mut Deserializer<'l>,
+    names: &'static [&'static str],
+}
+
+struct Variant {
+    name: String,
+}
+
+impl<'l> Deserializer<'l> {
+    /// Create a deserializer.
+    pub fn new(location: &'l Location, name: &str) -> Deserializer<'l> {
+        Deserializer {
+            location: location,
+            name: Some(name.to_string()),
+            state: State::Uncertain,
+        }
+    }
+
+    fn element(&mut self) -> Result<Buffer> {
+        match self.state {
+            State::Uncertain => match self.name.take() {
+                Some(ref name) => {
+                    let dataset = try!(Dataset::open(self.location, name));
+                    dataset::read(&dataset, &try!(datatype::native(&try!(dataset.datatype()))))
+                },
+                _ => raise!("cannot read data without a name"),
+            },
+            State::Sequence(ref buffer, ref mut position) => {
+                *position += 1;
+                compound::element(buffer, *position - 1)
+            },
+            State::Structure(ref buffer) => match self.name.take() {
+                Some(name) => match try!(compound::field(buffer, &name)) {
+                    Some(buffer) => Ok(buffer),
+                    _ => raise!("found no field {:?}", name),
+                },
+                _ => raise!("cannot read a field without a name"),
+            },
+        }
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        match self.state {
+            State::Uncertain => Link::exists(self.location, name),
+            State::Sequence(..) => Ok(true),
+            State::Structure(ref buffer) => Ok(try!(compound::field(buffer, name)).is_some()),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        let buffer = match self.state {
+            State::Uncertain => try!(self.element()),
+            _ => try!(compound::expand(try!(self.element()))),
+        };
+        Vec::<u8>::from_data(buffer)
+    }
+
+    fn sequence<'de, V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value> {
+        let buffer = match self.state {
+            State::Uncertain => try!(self.element()),
+            _ => try!(compound::expand(try!(self.element()))),
+        };
+        let length = product!(buffer.dimensions());
+        let state = mem::replace(&mut self.state, State::Sequence(buffer, 0));
+        let result = visitor.visit_seq(Elements { deserializer: self, remaining: length });
+        self.state = state;
+        result
+    }
+
+    fn structure<'de, V: Visitor<'de>>(&mut self, names: &'static [&'static str], visitor: V)
+                                       -> Result<V::Value> {
+
+        let buffer = try!(self.element());
+        if !datatype::is_compound(&buffer.datatype()) {
+            raise!("the data should have a compound datatype");
+        }
+        if product!(buffer.dimensions()) != 1 {
+            raise!("the data should contain exactly one structure");
+        }
+        let state = mem::replace(&mut self.state, State::Structure(buffer));
+        let result = visitor.visit_map(Fields { deserializer: self, names: names });
+        self.state = state;
+        result
+    }
+
+    fn variant(&mut self, names: &'static [&'static str]) -> Result<Variant> {
+        let index = try!(compound::variant_index(try!(self.element()), names));
+        Ok(Variant { name: names[index].to_string() })
+    }
+}
+
+impl de::Error for Error {
+    #[inline]
+    fn custom<T: Display>(message: T) -> Error {
+        Error(message.to_string())
+    }
+}
+
+macro_rules! primitive(
+    ($($method:ident => ($kind:ty, $visit:ident),)*) => (
+        $(
+            #[inline]
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                visitor.$visit(try!(<$kind>::from_data(try!(self.element()))))
+            }
+        )*
+    );
+);
+
+impl<'de, 's, 'l> de::Deserializer<'de> for &'s mut Deserializer<'l> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value> {
+        raise!("HDF5 requires the type of the data to be known");
+    }
+
+    primitive!(
+        deserialize_bool => (bool, visit_bool),
+        deserialize_i8 => (i8, visit_i8),
+        deserialize_i16 => (i16, visit_i16),
+        deserialize_i32 => (i32, visit_i32),
+        deserialize_i64 => (i64, visit_i64),
+        deserialize_u8 => (u8, visit_u8),
+        deserialize_u16 => (u16, visit_u16),
+        deserialize_u32 => (u32, visit_u32),
+        deserialize_u64 => (u64, visit_u64),
+        deserialize_f32 => (f32, visit_f32),
+        deserialize_f64 => (f64, visit_f64),
+        deserialize_char => (char, visit_char),
+        deserialize_str => (String, visit_string),
+        deserialize_string => (String, visit_string),
+        deserialize_identifier => (String, visit_string),
+    );
+
+    #[inline]
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(try!(self.bytes()))
+    }
+
+    #[inline]
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(try!(self.bytes()))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let exists = match self.name {
+            Some(ref name) => try!(self.exists(name)),
+            _ => true,
+        };
+        if exists {
+            visitor.visit_some(self)
+        } else {
+            self.name = None;
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, _: V) -> Result<V::Value> {
+        raise!("HDF5 does not support units");
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _: &'static str, _: V)
+                                                -> Result<V::Value> {
+        raise!("HDF5 does not support unit structs");
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V)
+                                                   -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.sequence(visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        self.sequence(visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _: &'static str, _: usize, visitor: V)
+                                                 -> Result<V::Value> {
+        self.sequence(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, _: V) -> Result<V::Value> {
+        raise!("HDF5 does not support maps");
+    }
+
+    #[inline]
+    fn deserialize_struct<V: Visitor<'de>>(self, _: &'static str, names: &'static [&'static str],
+                                           visitor: V) -> Result<V::Value> {
+        self.structure(names, visitor)
+    }
+
+    #[inline]
+    fn deserialize_enum<V: Visitor<'de>>(self, _: &'static str, names: &'static [&'static str],
+                                         visitor: V) -> Result<V::Value> {
+        visitor.visit_enum(try!(self.variant(names)))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        try!(self.element());
+        visitor.visit_unit()
+    }
+}
+
+impl<'de, 's, 'l> de::SeqAccess<'de> for Elements<'s, 'l> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, 's, 'l> de::MapAccess<'de> for Fields<'s, 'l> {
+    type Error = Error;
+
+    fn next_key_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        while let Some((name, names)) = self.names.split_first() {
+            self.names = names;
+            if try!(self.deserializer.exists(name)) {
+                self.deserializer.name = Some(name.to_string());
+                return seed.deserialize(name.into_deserializer()).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for Variant {
+    type Error = Error;
+    type Variant = Self;
+
+    #[inline]
+    fn variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<(T::Value, Self)> {
+        let value = try!(seed.deserialize(self.name.clone().into_deserializer()));
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Variant {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, _: T) -> Result<T::Value> {
+        raise!("HDF5 does not support enums with arguments");
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, _: V) -> Result<V::Value> {
+        raise!("HDF5 does not support enums with arguments");
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _: &'static [&'static str], _: V)
+                                       -> Result<V::Value> {
+        raise!("HDF5 does not support enum structs");
+    }
+}
@@ -1,43 +1,40 @@
 use rustc_serialize;
 use std::mem;
 
+use compound::{self, Sequence, Structure};
 use data::{Data, IntoData};
-use datatype::{self, Datatype};
-use file::File;
-use {Error, Result};
+use {Error, Location, Options, Result};
 
 /// An encoder.
 ///
-/// Encoders are suitable for storing structural data.
+/// Encoders are suitable for storing structural data. Sequences nested in
+/// other sequences or structures are stored as variable-length sequences, and
+/// tuples as fixed-size arrays.
+///
+/// Rust arrays such as `[f64; 3]` are not stored as fixed-size arrays, since
+/// `rustc-serialize` encodes them exactly as vectors; nested in sequences or
+/// structures, they become variable-length sequences. Tuples or the `serde`
+/// feature, which stores Rust arrays as fixed-size arrays, should be used
+/// instead when the HDF5 array datatype is required.
+///
+/// Unit variants of enums are stored as enum datatypes whose values are the
+/// indices of the variants. Since `rustc-serialize` reveals only the variant
+/// being encoded, the datatype lists only the variants present in the data,
+/// and so do other readers such as h5py.
 pub struct Encoder<'l> {
-    file: &'l File,
+    location: &'l Location,
     name: Option<String>,
+    options: Options,
     state: State,
 }
 
 enum State {
     Uncertain,
+    Array(Sequence),
     Sequence(Sequence),
     Structure(Structure),
 }
 
-struct Sequence {
-    data: Vec<u8>,
-    datatype: Option<Datatype>,
-    length: usize,
-}
-
-struct Structure {
-    data: Vec<u8>,
-    fields: Vec<(String, Datatype, usize)>,
-}
-
-struct Blob {
-    data: Vec<u8>,
-    datatype: Datatype,
-    dimensions: [usize; 1],
-}
-
 impl<'l> Encoder<'l> {
     /// Create an encoder.
     ///
@@ -47,57 +44,65 @@ impl<'l> Encoder<'l> {
     /// 5.5.2][1] in HDF5 User’s Guide for further details.
     ///
     /// [1]: https://www.hdfgroup.org/HDF5/doc/UG/10_Datasets.html#Allocation
-    pub fn new(file: &'l File, name: &str) -> Encoder<'l> {
-        Encoder { file: file, name: Some(name.to_string()), state: State::Uncertain }
+    pub fn new(location: &'l Location, name: &str) -> Encoder<'l> {
+        Encoder {
+            location: location,
+            name: Some(name.to_string()),
+            options: Options::new(),
+            state: State::Uncertain,
+        }
     }
 
-    fn element<T: Data>(&mut self, data: T) -> Result<()> {
-        #[inline]
-        fn copy(from: &[u8], into: &mut Vec<u8>) -> usize {
-            use std::ptr::copy_nonoverlapping as copy;
-            let (delta, current) = (from.len(), into.len());
-            into.reserve(delta);
-            unsafe {
-                into.set_len(current + delta);
-                copy(from.as_ptr(), into.as_mut_ptr().offset(current as isize), delta);
-            }
-            delta
-        }
+    /// Set the options of the dataset to be created.
+    ///
+    /// Structures are laid out with the native alignment; see
+    /// `Options::pack` for storing them without padding.
+    pub fn options(mut self, options: Options) -> Encoder<'l> {
+        self.options = options;
+        self
+    }
 
+    fn element<T: Data>(&mut self, data: T) -> Result<()> {
         match self.state {
             State::Uncertain => match self.name.take() {
-                Some(ref name) => self.file.write(name, data),
+                Some(ref name) => {
+                    Location::write_with_options(&self.location, name, data, self.options.clone())
+                },
                 _ => raise!("cannot write data without a name"),
             },
-            State::Sequence(ref mut sequence) => {
-                if let Some(ref datatype) = sequence.datatype {
-                    if datatype != &data.datatype() {
-                        raise!("cannot mix datatypes in arrays");
-                    }
-                } else {
-                    sequence.datatype = Some(data.datatype());
-                }
-                copy(data.as_bytes(), &mut sequence.data);
-                sequence.length += 1;
-                Ok(())
+            State::Array(ref mut sequence) | State::Sequence(ref mut sequence) => {
+                sequence.push(data)
             },
             State::Structure(ref mut structure) => match self.name.take() {
-                Some(name) => {
-                    let size = copy(data.as_bytes(), &mut structure.data);
-                    structure.fields.push((name, data.datatype(), size));
-                    Ok(())
-                },
+                Some(name) => structure.push(name, data),
                 _ => raise!("cannot write a field without a name"),
             },
         }
     }
 
+    fn array<F>(&mut self, next: F) -> Result<()> where F: FnOnce(&mut Self) -> Result<()> {
+        let state = mem::replace(&mut self.state, State::Array(Sequence::new()));
+        try!(next(self));
+        let sequence = match mem::replace(&mut self.state, state) {
+            State::Array(sequence) => sequence,
+            _ => unreachable!(),
+        };
+        match self.state {
+            State::Uncertain => self.element(try!(sequence.coagulate())),
+            _ => self.element(try!(sequence.coagulate_array())),
+        }
+    }
+
     fn sequence<F>(&mut self, next: F) -> Result<()> where F: FnOnce(&mut Self) -> Result<()> {
         let state = mem::replace(&mut self.state, State::Sequence(Sequence::new()));
         try!(next(self));
-        match mem::replace(&mut self.state, state) {
-            State::Sequence(sequence) => self.element(try!(sequence.coagulate())),
+        let sequence = match mem::replace(&mut self.state, state) {
+            State::Sequence(sequence) => sequence,
             _ => unreachable!(),
+        };
+        match self.state {
+            State::Uncertain => self.element(try!(sequence.coagulate())),
+            _ => self.element(try!(sequence.coagulate_variable())),
         }
     }
 
@@ -110,6 +115,18 @@ impl<'l> Encoder<'l> {
         }
     }
 
+    fn missing(&mut self) -> Result<()> {
+        match self.state {
+            State::Array(..) | State::Sequence(..) => {
+                raise!("cannot write a missing element of a sequence")
+            },
+            _ => {
+                self.name = None;
+                Ok(())
+            },
+        }
+    }
+
     fn structure_field<F>(&mut self, name: &str, next: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
@@ -122,52 +139,6 @@ impl<'l> Encoder<'l> {
     }
 }
 
-impl Sequence {
-    #[inline]
-    fn new() -> Sequence {
-        Sequence { data: vec![], datatype: None, length: 0 }
-    }
-
-    fn coagulate(self) -> Result<Blob> {
-        let Sequence { data, datatype, length } = self;
-        let datatype = match datatype {
-            Some(datatype) => datatype,
-            _ => raise!("cannot infer the datatype of empty arrays"),
-        };
-        debug_assert_eq!(length * datatype.size().unwrap(), data.len());
-        Ok(Blob { data: data, datatype: datatype, dimensions: [length] })
-    }
-}
-
-impl Structure {
-    fn new() -> Structure {
-        Structure { data: vec![], fields: vec![] }
-    }
-
-    fn coagulate(self) -> Result<Blob> {
-        let Structure { data, fields } = self;
-        let datatype = try!(datatype::new_compound(&fields));
-        Ok(Blob { data: data, datatype: datatype, dimensions: [1] })
-    }
-}
-
-impl Data for Blob {
-    #[inline]
-    fn as_bytes(&self) -> &[u8] {
-        &self.data
-    }
-
-    #[inline]
-    fn datatype(&self) -> Datatype {
-        self.datatype.clone()
-    }
-
-    #[inline]
-    fn dimensions(&self) -> &[usize] {
-        &self.dimensions
-    }
-}
-
 impl<'l> rustc_serialize::Encoder for Encoder<'l> {
     type Error = Error;
 
@@ -191,26 +162,28 @@ impl<'l> rustc_serialize::Encoder for Encoder<'l> {
     fn emit_enum_struct_variant<F>(&mut self, _: &str, _: usize, _: usize, _: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support enum structs");
+        raise!("HDF5 does not support enum structs");
     }
 
     fn emit_enum_struct_variant_field<F>(&mut self, _: &str, _: usize, _: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support enum structs");
+        raise!("HDF5 does not support enum structs");
     }
 
-    #[inline]
-    fn emit_enum_variant<F>(&mut self, _: &str, id: usize, _: usize, _: F) -> Result<()>
+    fn emit_enum_variant<F>(&mut self, name: &str, id: usize, count: usize, _: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        self.element(id)
+        if count > 0 {
+            raise!("HDF5 does not support enums with arguments");
+        }
+        self.element(try!(compound::variant(name, id as u32)))
     }
 
     fn emit_enum_variant_arg<F>(&mut self, _: usize, _: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support enums with arguments");
+        raise!("HDF5 does not support enums with arguments");
     }
 
     #[inline]
@@ -256,23 +229,23 @@ impl<'l> rustc_serialize::Encoder for Encoder<'l> {
     fn emit_map<F>(&mut self, _: usize, _: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support maps");
+        raise!("HDF5 does not support maps");
     }
 
     fn emit_map_elt_key<F>(&mut self, _: usize, _: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support maps");
+        raise!("HDF5 does not support maps");
     }
 
     fn emit_map_elt_val<F>(&mut self, _: usize, _: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support maps");
+        raise!("HDF5 does not support maps");
     }
 
     fn emit_nil(&mut self) -> Result<()> {
-        panic!("HDF5 does not support nils");
+        raise!("HDF5 does not support nils");
     }
 
     #[inline]
@@ -284,7 +257,7 @@ impl<'l> rustc_serialize::Encoder for Encoder<'l> {
 
     #[inline]
     fn emit_option_none(&mut self) -> Result<()> {
-        Ok(())
+        self.missing()
     }
 
     #[inline]
@@ -310,7 +283,10 @@ impl<'l> rustc_serialize::Encoder for Encoder<'l> {
 
     #[inline]
     fn emit_str(&mut self, value: &str) -> Result<()> {
-        self.element(try!(value.into_data()))
+        match self.state {
+            State::Uncertain => self.element(try!(value.into_data())),
+            _ => self.element(try!((&[value][..]).into_data())),
+        }
     }
 
     #[inline]
@@ -327,28 +303,32 @@ impl<'l> rustc_serialize::Encoder for Encoder<'l> {
         self.structure_field(name, next)
     }
 
-    fn emit_tuple<F>(&mut self, _: usize, _: F) -> Result<()>
+    #[inline]
+    fn emit_tuple<F>(&mut self, _: usize, next: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support tuples");
+        self.array(next)
     }
 
-    fn emit_tuple_arg<F>(&mut self, _: usize, _: F) -> Result<()>
+    #[inline]
+    fn emit_tuple_arg<F>(&mut self, _: usize, next: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support tuples");
+        next(self)
     }
 
-    fn emit_tuple_struct<F>(&mut self, _: &str, _: usize, _: F) -> Result<()>
+    #[inline]
+    fn emit_tuple_struct<F>(&mut self, _: &str, _: usize, next: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support tuple structs");
+        self.array(next)
     }
 
-    fn emit_tuple_struct_arg<F>(&mut self, _: usize, _: F) -> Result<()>
+    #[inline]
+    fn emit_tuple_struct_arg<F>(&mut self, _: usize, next: F) -> Result<()>
         where F: FnOnce(&mut Self) -> Result<()>
     {
-        panic!("HDF5 does not support tuple structs");
+        next(self)
     }
 
     #[inline]
@@ -1,64 +1,261 @@
 use ffi;
-use std::path::Path;
+use libc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{fs, io, mem};
 
-use data::{Data, IntoData};
-use writer::Writer;
-use {ID, Result};
-
-#[cfg(feature = "serialize")]
-use encoder::Encoder;
-
-#[cfg(feature = "serialize")]
-use rustc_serialize::Encodable;
+use plist;
+use {ID, Identity, Result};
 
 /// A file.
 pub struct File {
     id: ID,
 }
 
+/// A kind of open objects.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Objects {
+    /// All objects.
+    All,
+    /// Attributes.
+    Attributes,
+    /// Datasets.
+    Datasets,
+    /// Committed datatypes.
+    Datatypes,
+    /// Files.
+    Files,
+    /// Groups.
+    Groups,
+}
+
+/// A scope of flushing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scope {
+    /// The file itself.
+    Local,
+    /// The file and all files mounted on it.
+    Global,
+}
+
+/// Options for opening a file.
+///
+/// The options mimic those of `std::fs::OpenOptions`. By default, a file is
+/// opened for reading only, and it should exist.
+#[derive(Clone, Debug, Default)]
+pub struct OpenOptions {
+    write: bool,
+    create: bool,
+    create_new: bool,
+    truncate: bool,
+    memory: Option<bool>,
+}
+
 identity!(File);
+object!(File);
 location!(File);
 
 impl File {
     /// Create a new file.
     ///
     /// If the file already exists, its content will be truncated.
+    #[inline]
     pub fn new<T: AsRef<Path>>(path: T) -> Result<File> {
+        OpenOptions::new().write(true).create(true).truncate(true).open(path)
+    }
+
+    /// Open an existing file for reading and writing.
+    #[inline]
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<File> {
+        OpenOptions::new().write(true).open(path)
+    }
+
+    /// Create a new file in memory.
+    ///
+    /// The file lives only as long as it is open. Its content can be obtained
+    /// via `File::to_bytes`.
+    pub fn in_memory() -> Result<File> {
+        let access = try!(plist::new_core_file_access(false));
         Ok(File {
-            id: ok!(ffi::H5Fcreate(path_to_cstr!(path.as_ref()).as_ptr(), ffi::H5F_ACC_TRUNC,
-                                   ffi::H5P_DEFAULT, ffi::H5P_DEFAULT),
-                    "failed to create a file {:?}", path.as_ref()),
+            id: ok!(ffi::H5Fcreate(str_to_cstr!(&unique_name()[..]).as_ptr(), ffi::H5F_ACC_EXCL,
+                                   ffi::H5P_DEFAULT, access.id()),
+                    "failed to create a file in memory"),
         })
     }
 
-    /// Open an existing file.
-    pub fn open<T: AsRef<Path>>(path: T) -> Result<File> {
+    /// Open a file in memory from its content.
+    ///
+    /// The content is copied, and the file is opened for reading and writing.
+    /// The original content stays intact.
+    pub fn from_bytes(bytes: &[u8]) -> Result<File> {
+        let access = try!(plist::new_core_file_access(false));
+        ok!(ffi::H5Pset_file_image(access.id(), bytes.as_ptr() as *mut _,
+                                   bytes.len() as libc::size_t),
+            "failed to set the file image");
         Ok(File {
-            id: ok!(ffi::H5Fopen(path_to_cstr!(path.as_ref()).as_ptr(), ffi::H5F_ACC_RDWR,
-                                 ffi::H5P_DEFAULT),
-                    "failed to open a file {:?}", path.as_ref()),
+            id: ok!(ffi::H5Fopen(str_to_cstr!(&unique_name()[..]).as_ptr(), ffi::H5F_ACC_RDWR,
+                                 access.id()),
+                    "failed to open a file in memory"),
         })
     }
 
-    /// Encode data.
+    /// Close the file.
+    ///
+    /// Unlike dropping, the function reports whether the file has been closed
+    /// successfully, which includes writing all buffered data.
+    pub fn close(self) -> Result<()> {
+        let id = self.id;
+        mem::forget(self);
+        ok!(ffi::H5Fclose(id), "failed to close the file");
+        Ok(())
+    }
+
+    /// Flush all buffered data.
+    pub fn flush(&self, scope: Scope) -> Result<()> {
+        let scope = match scope {
+            Scope::Local => ffi::H5F_SCOPE_LOCAL,
+            Scope::Global => ffi::H5F_SCOPE_GLOBAL,
+        };
+        ok!(ffi::H5Fflush(self.id, scope), "failed to flush the file");
+        Ok(())
+    }
+
+    /// Return the amount of unused space in bytes.
+    pub fn free_space(&self) -> Result<usize> {
+        Ok(ok!(ffi::H5Fget_freespace(self.id), "failed to obtain the free space") as usize)
+    }
+
+    /// Return the number of open objects of a particular kind.
+    ///
+    /// The file itself is counted among files.
+    pub fn object_count(&self, objects: Objects) -> Result<usize> {
+        let types = match objects {
+            Objects::All => ffi::H5F_OBJ_ALL,
+            Objects::Attributes => ffi::H5F_OBJ_ATTR,
+            Objects::Datasets => ffi::H5F_OBJ_DATASET,
+            Objects::Datatypes => ffi::H5F_OBJ_DATATYPE,
+            Objects::Files => ffi::H5F_OBJ_FILE,
+            Objects::Groups => ffi::H5F_OBJ_GROUP,
+        };
+        Ok(ok!(ffi::H5Fget_obj_count(self.id, types), "failed to count the open objects") as usize)
+    }
+
+    /// Return the path.
+    pub fn path(&self) -> Result<PathBuf> {
+        let size = ok!(ffi::H5Fget_name(self.id, 0 as *mut _, 0), "failed to obtain the path");
+        let mut buffer = vec![0u8; size as usize + 1];
+        ok!(ffi::H5Fget_name(self.id, buffer.as_mut_ptr() as *mut _, buffer.len() as libc::size_t),
+            "failed to obtain the path");
+        buffer.truncate(size as usize);
+        match String::from_utf8(buffer) {
+            Ok(path) => Ok(PathBuf::from(path)),
+            _ => raise!("failed to process the path"),
+        }
+    }
+
+    /// Return the size in bytes.
+    pub fn size(&self) -> Result<usize> {
+        let mut size = 0;
+        ok!(ffi::H5Fget_filesize(self.id, &mut size), "failed to obtain the size");
+        Ok(size as usize)
+    }
+
+    /// Return the content.
+    ///
+    /// The function flushes the file and copies its content, which can be
+    /// loaded back via `File::from_bytes`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        try!(self.flush(Scope::Local));
+        let size = ok!(ffi::H5Fget_file_image(self.id, 0 as *mut _, 0),
+                       "failed to obtain the size of the file image");
+        let mut bytes = vec![0u8; size as usize];
+        ok!(ffi::H5Fget_file_image(self.id, bytes.as_mut_ptr() as *mut _,
+                                   bytes.len() as libc::size_t),
+            "failed to obtain the file image");
+        Ok(bytes)
+    }
+}
+
+impl OpenOptions {
+    /// Create options.
+    #[inline]
+    pub fn new() -> OpenOptions {
+        OpenOptions::default()
+    }
+
+    /// Set the option for creating a new file if it does not exist.
     ///
-    /// The function is a shortcut for `Encoder::new` followed by
-    /// `Encodable::encode`.
-    #[cfg(feature = "serialize")]
-    pub fn encode<T: Encodable>(&self, name: &str, data: T) -> Result<()> {
-        let mut encoder = Encoder::new(self, name);
-        data.encode(&mut encoder)
+    /// The option requires write access.
+    pub fn create(&mut self, create: bool) -> &mut OpenOptions {
+        self.create = create;
+        self
     }
 
-    /// Write data.
+    /// Set the option for creating a new file and failing if it exists.
     ///
-    /// The function is a shortcut for `Writer::new` followed by
-    /// `Writer::write`.
-    pub fn write<T: IntoData>(&self, name: &str, data: T) -> Result<()> {
-        let data = try!(data.into_data());
-        let dimensions = data.dimensions();
-        let mut writer = Writer::new(self, name, dimensions);
-        writer.write(&data, &vec![0; dimensions.len()], dimensions)
+    /// The option requires write access, and it takes precedence over
+    /// `create` and `truncate`.
+    pub fn create_new(&mut self, create_new: bool) -> &mut OpenOptions {
+        self.create_new = create_new;
+        self
+    }
+
+    /// Set the option for keeping the file in memory.
+    ///
+    /// An existing file is loaded into memory upon opening. If the backing
+    /// store is enabled, the content is written to the file upon closing;
+    /// otherwise, all changes are discarded.
+    pub fn in_memory(&mut self, backing_store: bool) -> &mut OpenOptions {
+        self.memory = Some(backing_store);
+        self
+    }
+
+    /// Set the option for truncating an existing file.
+    ///
+    /// The option requires write access.
+    pub fn truncate(&mut self, truncate: bool) -> &mut OpenOptions {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Set the option for write access.
+    pub fn write(&mut self, write: bool) -> &mut OpenOptions {
+        self.write = write;
+        self
+    }
+
+    /// Open a file with the options.
+    pub fn open<T: AsRef<Path>>(&self, path: T) -> Result<File> {
+        let path = path.as_ref();
+        if !self.write && (self.create || self.create_new || self.truncate) {
+            raise!("creating or truncating a file {:?} requires write access", path);
+        }
+        let access = match self.memory {
+            Some(backing_store) => Some(try!(plist::new_core_file_access(backing_store))),
+            _ => None,
+        };
+        let access = access.as_ref().map(|list| list.id()).unwrap_or(ffi::H5P_DEFAULT);
+        if self.create_new {
+            return create(path, ffi::H5F_ACC_EXCL, access);
+        }
+        if self.truncate {
+            if !self.create {
+                try!(open(path, ffi::H5F_ACC_RDWR, access));
+            }
+            return create(path, ffi::H5F_ACC_TRUNC, access);
+        }
+        if self.create {
+            return open(path, ffi::H5F_ACC_RDWR, access).or_else(|_| {
+                create(path, ffi::H5F_ACC_EXCL, access).or_else(|error| {
+                    if fs::metadata(path).is_err() {
+                        return Err(error);
+                    }
+                    open(path, ffi::H5F_ACC_RDWR, access)
+                })
+            });
+        }
+        let flags = if self.write { ffi::H5F_ACC_RDWR } else { ffi::H5F_ACC_RDONLY };
+        open(path, flags, access)
     }
 }
 
@@ -67,3 +264,35 @@ impl Drop for File {
         whatever!(ffi::H5Fclose(self.id));
     }
 }
+
+fn unique_name() -> String {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    format!("memory{}.h5", COUNT.fetch_add(1, Ordering::SeqCst))
+}
+
+fn create(path: &Path, flags: libc::c_uint, access: ID) -> Result<File> {
+    let id = unsafe {
+        ffi::H5Fcreate(path_to_cstr!(path).as_ptr(), flags, ffi::H5P_DEFAULT, access)
+    };
+    if id < 0 {
+        if flags == ffi::H5F_ACC_EXCL && fs::metadata(path).is_ok() {
+            raise!("the file {:?} already exists", path);
+        }
+        raise!("failed to create a file {:?}", path);
+    }
+    Ok(File { id: id })
+}
+
+fn open(path: &Path, flags: libc::c_uint, access: ID) -> Result<File> {
+    let id = unsafe { ffi::H5Fopen(path_to_cstr!(path).as_ptr(), flags, access) };
+    if id < 0 {
+        match fs::metadata(path) {
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => {
+                raise!("the file {:?} does not exist", path);
+            },
+            Err(error) => raise!("failed to open a file {:?} ({})", path, error),
+            _ => raise!("failed to open a file {:?}", path),
+        }
+    }
+    Ok(File { id: id })
+}
@@ -0,0 +1,54 @@
+use ffi;
+
+use link::Link;
+use plist;
+use {ID, Identity, Location, Result};
+
+/// A group.
+///
+/// Groups are suitable for organizing datasets hierarchically. Nested groups
+/// are addressed by paths such as `a/b/c`.
+pub struct Group {
+    id: ID,
+}
+
+identity!(Group);
+object!(Group);
+location!(Group);
+
+impl Group {
+    /// Create a new group.
+    ///
+    /// Intermediate groups are created as needed.
+    pub fn new<T: Location>(location: T, name: &str) -> Result<Group> {
+        let list = try!(plist::new_link_create());
+        Ok(Group {
+            id: ok!(ffi::H5Gcreate2(location.id(), str_to_cstr!(name).as_ptr(), list.id(),
+                                    ffi::H5P_DEFAULT, ffi::H5P_DEFAULT),
+                    "failed to create a group {:?}", name),
+        })
+    }
+
+    /// Open an existing group.
+    pub fn open<T: Location>(location: T, name: &str) -> Result<Group> {
+        Ok(Group {
+            id: ok!(ffi::H5Gopen2(location.id(), str_to_cstr!(name).as_ptr(), ffi::H5P_DEFAULT),
+                    "failed to open a group {:?}", name),
+        })
+    }
+
+    /// Open an existing group or create a new one.
+    pub fn open_or_create<T: Location>(location: T, name: &str) -> Result<Group> {
+        if try!(Link::exists(&location, name)) {
+            Group::open(location, name)
+        } else {
+            Group::new(location, name)
+        }
+    }
+}
+
+impl Drop for Group {
+    fn drop(&mut self) {
+        whatever!(ffi::H5Gclose(self.id));
+    }
+}
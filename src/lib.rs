@@ -56,16 +56,44 @@
 //!
 //! file.encode("foo", &foo).unwrap();
 //! # }
+//! ```
+//!
+//! Structural data written in this way can be read back by deriving
+//! `RustcDecodable` and calling `File::decode`.
+//!
+//! Alternatively, when the `serde` feature is enabled, structural data can be
+//! stored by deriving `Serialize` and `Deserialize` from [`serde`][3] and
+//! calling `File::serialize` and `File::deserialize`, respectively. The two
+//! features can be enabled at the same time.
+//!
+//! When the `ndarray` feature is enabled, arrays of [`ndarray`][4] can be
+//! written as they are, and datasets and hyperslabs can be read into them.
+//! Likewise, the `nalgebra` feature enables matrices and vectors of
+//! [`nalgebra`][5], which are stored as two-dimensional datasets with one row
+//! per row of the matrix. `File::write_matrix` additionally records this
+//! orientation in an attribute, which `File::read_matrix` checks.
 //!
 //! [1]: http://www.hdfgroup.org/HDF5
 //! [2]: https://crates.io/crates/rustc-serialize
+//! [3]: https://crates.io/crates/serde
+//! [4]: https://crates.io/crates/ndarray
+//! [5]: https://crates.io/crates/nalgebra
 
 extern crate hdf5_sys as ffi;
 extern crate libc;
 
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+
 #[cfg(feature = "serialize")]
 extern crate rustc_serialize;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+
 use std::{error, fmt};
 
 /// An error.
@@ -80,10 +108,6 @@ pub trait Identity {
     fn id(&self) -> ID;
 }
 
-#[doc(hidden)]
-pub trait Location: Identity {
-}
-
 /// A result.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -98,10 +122,113 @@ macro_rules! identity(
     );
 );
 
+macro_rules! object(
+    ($name:ident) => (
+        impl ::Object for $name {
+        }
+    );
+);
+
 macro_rules! location(
     ($name:ident) => (
         impl ::Location for $name {
         }
+
+        impl $name {
+            /// Open an existing dataset.
+            ///
+            /// The function forwards to `Location::dataset`.
+            #[inline]
+            pub fn dataset(&self, name: &str) -> ::Result<::Dataset> {
+                ::Location::dataset(self, name)
+            }
+
+            /// Decode data.
+            ///
+            /// The function forwards to `Location::decode`.
+            #[cfg(feature = "serialize")]
+            #[inline]
+            pub fn decode<T: ::rustc_serialize::Decodable>(&self, name: &str) -> ::Result<T> {
+                ::Location::decode(self, name)
+            }
+
+            /// Encode data.
+            ///
+            /// The function forwards to `Location::encode`.
+            #[cfg(feature = "serialize")]
+            #[inline]
+            pub fn encode<T: ::rustc_serialize::Encodable>(&self, name: &str, data: T)
+                                                           -> ::Result<()> {
+                ::Location::encode(self, name, data)
+            }
+
+            /// Deserialize data.
+            ///
+            /// The function forwards to `Location::deserialize`.
+            #[cfg(feature = "serde")]
+            #[inline]
+            pub fn deserialize<T: ::serde::de::DeserializeOwned>(&self, name: &str)
+                                                                 -> ::Result<T> {
+                ::Location::deserialize(self, name)
+            }
+
+            /// Serialize data.
+            ///
+            /// The function forwards to `Location::serialize`.
+            #[cfg(feature = "serde")]
+            #[inline]
+            pub fn serialize<T: ::serde::Serialize>(&self, name: &str, data: T) -> ::Result<()> {
+                ::Location::serialize(self, name, data)
+            }
+
+            /// Read data.
+            ///
+            /// The function forwards to `Location::read`.
+            #[inline]
+            pub fn read<T: ::FromData>(&self, name: &str) -> ::Result<T> {
+                ::Location::read(self, name)
+            }
+
+            /// Read a matrix.
+            ///
+            /// The function forwards to `Location::read_matrix`.
+            #[cfg(feature = "nalgebra")]
+            #[inline]
+            pub fn read_matrix<T: ::FromData>(&self, name: &str) -> ::Result<T> {
+                ::Location::read_matrix(self, name)
+            }
+
+            /// Write data.
+            ///
+            /// The function forwards to `Location::write`.
+            #[inline]
+            pub fn write<T: ::IntoData>(&self, name: &str, data: T) -> ::Result<()> {
+                ::Location::write(self, name, data)
+            }
+
+            /// Write data with particular options.
+            ///
+            /// The function forwards to `Location::write_with_options`.
+            #[inline]
+            pub fn write_with_options<T: ::IntoData>(&self, name: &str, data: T,
+                                                     options: ::Options) -> ::Result<()> {
+                ::Location::write_with_options(self, name, data, options)
+            }
+
+            /// Write a matrix.
+            ///
+            /// The function forwards to `Location::write_matrix`.
+            #[cfg(feature = "nalgebra")]
+            #[inline]
+            pub fn write_matrix<T, R, C, S>(&self, name: &str,
+                                            matrix: &::nalgebra::Matrix<T, R, C, S>)
+                                            -> ::Result<()>
+                where T: ::H5Type, R: ::nalgebra::Dim, C: ::nalgebra::Dim,
+                      S: ::nalgebra::RawStorage<T, R, C>
+            {
+                ::Location::write_matrix(self, name, matrix)
+            }
+        }
     );
 );
 
@@ -175,16 +302,13 @@ impl error::Error for Error {
     }
 }
 
-impl<'l, T: Identity> Identity for &'l T {
+impl<'l, T: ?Sized + Identity> Identity for &'l T {
     #[inline]
     fn id(&self) -> ID {
         (*self).id()
     }
 }
 
-impl<'l, T: Location> Location for &'l T {
-}
-
 /// Return the version number of HDF5.
 pub fn version() -> Result<(usize, usize, usize)> {
     let (mut major, mut minor, mut patch) = (0, 0, 0);
@@ -193,25 +317,53 @@ pub fn version() -> Result<(usize, usize, usize)> {
     Ok((major, minor, patch))
 }
 
+mod attribute;
 mod data;
 mod dataset;
 mod dataspace;
 mod datatype;
 mod file;
+mod group;
 mod link;
+mod location;
+mod object;
+mod options;
+mod plist;
+mod reader;
 mod writer;
 
+#[cfg(feature = "ndarray")]
+mod array;
+#[cfg(any(feature = "serde", feature = "serialize"))]
+mod compound;
 #[cfg(feature = "serialize")]
 mod decoder;
+#[cfg(feature = "serde")]
+mod deserializer;
 #[cfg(feature = "serialize")]
 mod encoder;
+#[cfg(feature = "nalgebra")]
+mod matrix;
+#[cfg(feature = "serde")]
+mod serializer;
 
-pub use data::{Data, IntoData, Slice};
-pub use datatype::Datatype;
-pub use file::File;
-pub use writer::Writer;
+pub use dataset::{Dataset, Layout};
+pub use data::{Data, DataMut, FromData, H5Type, IntoData, IntoDataMut, Shaped, Slice, SliceMut};
+pub use dataspace::{Dataspace, Hyperslab, Operator};
+pub use datatype::{Class, Datatype, Member, Order};
+pub use file::{File, Objects, OpenOptions, Scope};
+pub use group::Group;
+pub use location::Location;
+pub use object::Object;
+pub use options::{Options, ScaleOffset};
+pub use reader::Reader;
+pub use writer::{Mode, Writer};
 
 #[cfg(feature = "serialize")]
 pub use decoder::Decoder;
+#[cfg(feature = "serde")]
+pub use deserializer::Deserializer;
 #[cfg(feature = "serialize")]
 pub use encoder::Encoder;
+#[cfg(feature = "serde")]
+pub use serializer::Serializer;
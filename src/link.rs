@@ -5,11 +5,25 @@ use {Location, Result};
 pub struct Link;
 
 impl Link {
+    /// Check if a link exists.
+    ///
+    /// Each component of the path is checked in turn, since HDF5 refuses to
+    /// examine a path whose intermediate components are missing.
     pub fn exists<T: Location>(location: T, name: &str) -> Result<bool> {
-        let result = ok!(ffi::H5Lexists(location.id(), str_to_cstr!(name).as_ptr(),
-                                        ffi::H5P_DEFAULT),
-                         "failed to check the existence of a link {:?}", name);
-        Ok(result > 0)
+        let mut path = if name.starts_with('/') { String::from("/") } else { String::new() };
+        for component in name.split('/').filter(|component| !component.is_empty()) {
+            if !path.is_empty() && !path.ends_with('/') {
+                path.push('/');
+            }
+            path.push_str(component);
+            let result = ok!(ffi::H5Lexists(location.id(), str_to_cstr!(&path[..]).as_ptr(),
+                                            ffi::H5P_DEFAULT),
+                             "failed to check the existence of a link {:?}", path);
+            if result <= 0 {
+                return Ok(false);
+            }
+        }
+        Ok(true)
     }
 
     pub fn delete<T: Location>(location: T, name: &str) -> Result<()> {
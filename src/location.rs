@@ -0,0 +1,140 @@
+use data::{Data, FromData, IntoData};
+use dataset::{self, Dataset};
+use datatype;
+use options::Options;
+use writer::Writer;
+use {Object, Result};
+
+#[cfg(feature = "nalgebra")]
+use data::H5Type;
+#[cfg(feature = "serialize")]
+use decoder::Decoder;
+#[cfg(feature = "serde")]
+use deserializer::Deserializer;
+#[cfg(feature = "serialize")]
+use encoder::Encoder;
+#[cfg(feature = "nalgebra")]
+use matrix;
+#[cfg(feature = "serde")]
+use serializer::Serializer;
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{Dim, Matrix, RawStorage};
+#[cfg(feature = "serialize")]
+use rustc_serialize::{Decodable, Encodable};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
+/// A location.
+///
+/// Locations are objects capable of containing datasets, that is, files and
+/// groups.
+pub trait Location: Object {
+    /// Open an existing dataset.
+    ///
+    /// The function is a shortcut for `Dataset::open`.
+    fn dataset(&self, name: &str) -> Result<Dataset> where Self: Sized {
+        Dataset::open(self, name)
+    }
+
+    /// Decode data.
+    ///
+    /// The function is a shortcut for `Decoder::new` followed by
+    /// `Decodable::decode`.
+    #[cfg(feature = "serialize")]
+    fn decode<T: Decodable>(&self, name: &str) -> Result<T> where Self: Sized {
+        let mut decoder = Decoder::new(self, name);
+        T::decode(&mut decoder)
+    }
+
+    /// Encode data.
+    ///
+    /// The function is a shortcut for `Encoder::new` followed by
+    /// `Encodable::encode`. See `Encoder` regarding Rust arrays, which are
+    /// stored as sequences.
+    #[cfg(feature = "serialize")]
+    fn encode<T: Encodable>(&self, name: &str, data: T) -> Result<()> where Self: Sized {
+        let mut encoder = Encoder::new(self, name);
+        data.encode(&mut encoder)
+    }
+
+    /// Deserialize data.
+    ///
+    /// The function is a shortcut for `Deserializer::new` followed by
+    /// `Deserialize::deserialize`.
+    #[cfg(feature = "serde")]
+    fn deserialize<T: DeserializeOwned>(&self, name: &str) -> Result<T> where Self: Sized {
+        let mut deserializer = Deserializer::new(self, name);
+        T::deserialize(&mut deserializer)
+    }
+
+    /// Serialize data.
+    ///
+    /// The function is a shortcut for `Serializer::new` followed by
+    /// `Serialize::serialize`.
+    #[cfg(feature = "serde")]
+    fn serialize<T: Serialize>(&self, name: &str, data: T) -> Result<()> where Self: Sized {
+        let mut serializer = Serializer::new(self, name);
+        data.serialize(&mut serializer)
+    }
+
+    /// Read data.
+    ///
+    /// The function opens an existing dataset and converts its content into
+    /// the requested type, provided that the stored datatype is compatible.
+    fn read<T: FromData>(&self, name: &str) -> Result<T> where Self: Sized {
+        let dataset = try!(Dataset::open(self, name));
+        let datatype = try!(datatype::native(&try!(dataset.datatype())));
+        T::from_data(try!(dataset::read(&dataset, &datatype)))
+    }
+
+    /// Read a matrix.
+    ///
+    /// The function checks that the dataset has been written by
+    /// `Location::write_matrix` and then proceeds as `Location::read` does.
+    #[cfg(feature = "nalgebra")]
+    fn read_matrix<T: FromData>(&self, name: &str) -> Result<T> where Self: Sized {
+        matrix::read(self, name)
+    }
+
+    /// Write data.
+    ///
+    /// The function is a shortcut for `Writer::new` followed by
+    /// `Writer::write`. Single values are stored in scalar datasets, and empty
+    /// sequences are stored in null ones. Other data without elements keep
+    /// their dimensions.
+    fn write<T: IntoData>(&self, name: &str, data: T) -> Result<()> where Self: Sized {
+        self.write_with_options(name, data, Options::new())
+    }
+
+    /// Write data with particular options.
+    ///
+    /// The function is a shortcut for `Writer::new` followed by
+    /// `Writer::options` and `Writer::write`.
+    fn write_with_options<T: IntoData>(&self, name: &str, data: T, options: Options)
+                                       -> Result<()> where Self: Sized {
+
+        let data = try!(data.into_data());
+        let dimensions = data.dimensions();
+        let mut writer = Writer::new(self, name, dimensions).options(options);
+        writer.write(&data, &vec![0; dimensions.len()], dimensions)
+    }
+
+    /// Write a matrix.
+    ///
+    /// The matrix is stored as `Location::write` does, with one row of the
+    /// dataset per row of the matrix, and the dataset is given an attribute
+    /// `orientation` equal to `row-major`, which `Location::read_matrix`
+    /// checks.
+    #[cfg(feature = "nalgebra")]
+    fn write_matrix<T, R, C, S>(&self, name: &str, matrix: &Matrix<T, R, C, S>) -> Result<()>
+        where Self: Sized, T: H5Type, R: Dim, C: Dim, S: RawStorage<T, R, C>
+    {
+        matrix::write(self, name, matrix)
+    }
+}
+
+impl<'l, T: ?Sized + Location> Location for &'l T {
+}
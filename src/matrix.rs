@@ -0,0 +1,94 @@
+use nalgebra::{self, DMatrix, DVector, Dim, Matrix, RawStorage, RowDVector, SMatrix};
+use std::borrow::Cow;
+
+use attribute;
+use data::{self, Data, FromData, H5Type, IntoData, Slice};
+use dataset::Dataset;
+use {Location, Object, Result};
+
+const ORIENTATION: &'static str = "orientation";
+const ROW_MAJOR: &'static str = "row-major";
+
+impl<'l, T, R, C, S> IntoData for &'l Matrix<T, R, C, S>
+    where T: 'l + H5Type, R: Dim, C: Dim, S: RawStorage<T, R, C>
+{
+    type Target = Slice<'l, T>;
+
+    fn into_data(self) -> Result<Self::Target> {
+        let (rows, columns) = self.shape();
+        let mut values = Vec::with_capacity(rows * columns);
+        for i in 0..rows {
+            for j in 0..columns {
+                values.push(self[(i, j)]);
+            }
+        }
+        data::new_slice(Cow::Owned(values), vec![rows, columns])
+    }
+}
+
+impl<U, const R: usize, const C: usize> FromData for SMatrix<U, R, C>
+    where U: H5Type + nalgebra::Scalar
+{
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        try!(shape(data.dimensions(), Some(R), Some(C)));
+        Ok(SMatrix::from_row_slice(&try!(Vec::<U>::from_data(data))))
+    }
+}
+
+impl<U> FromData for DMatrix<U> where U: H5Type + nalgebra::Scalar {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        let (rows, columns) = try!(shape(data.dimensions(), None, None));
+        Ok(DMatrix::from_row_slice(rows, columns, &try!(Vec::<U>::from_data(data))))
+    }
+}
+
+impl<U> FromData for DVector<U> where U: H5Type + nalgebra::Scalar {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        try!(shape(data.dimensions(), None, Some(1)));
+        Ok(DVector::from_vec(try!(Vec::<U>::from_data(data))))
+    }
+}
+
+impl<U> FromData for RowDVector<U> where U: H5Type + nalgebra::Scalar {
+    fn from_data<T: Data>(data: T) -> Result<Self> {
+        try!(shape(data.dimensions(), Some(1), None));
+        Ok(RowDVector::from_vec(try!(Vec::<U>::from_data(data))))
+    }
+}
+
+fn shape(dimensions: &[usize], rows: Option<usize>, columns: Option<usize>)
+         -> Result<(usize, usize)> {
+
+    let shape = match dimensions.len() {
+        2 => (dimensions[0], dimensions[1]),
+        1 if columns == Some(1) => (dimensions[0], 1),
+        1 if rows == Some(1) => (1, dimensions[0]),
+        _ => raise!("the data should have two dimensions"),
+    };
+    if rows.map_or(false, |rows| rows != shape.0) {
+        raise!("the data should have the number of rows of the matrix");
+    }
+    if columns.map_or(false, |columns| columns != shape.1) {
+        raise!("the data should have the number of columns of the matrix");
+    }
+    Ok(shape)
+}
+
+pub fn read<L: Location, T: FromData>(location: L, name: &str) -> Result<T> {
+    let dataset = try!(Dataset::open(&location, name));
+    if !try!(attribute::exists(&dataset, ORIENTATION)) {
+        raise!("the dataset {:?} should have an orientation", name);
+    }
+    let orientation = try!(dataset.read_attribute::<String>(ORIENTATION));
+    if orientation != ROW_MAJOR {
+        raise!("the orientation {:?} of the dataset {:?} is not supported", orientation, name);
+    }
+    location.read(name)
+}
+
+pub fn write<L, T, R, C, S>(location: L, name: &str, matrix: &Matrix<T, R, C, S>) -> Result<()>
+    where L: Location, T: H5Type, R: Dim, C: Dim, S: RawStorage<T, R, C>
+{
+    try!(location.write(name, matrix));
+    try!(Dataset::open(&location, name)).write_attribute(ORIENTATION, ROW_MAJOR)
+}
@@ -0,0 +1,52 @@
+use attribute;
+use data::{Data, FromData, IntoData};
+use dataspace;
+use {Identity, Result};
+
+/// An object.
+///
+/// Objects are entities capable of carrying attributes, that is, files,
+/// groups, and datasets.
+pub trait Object: Identity {
+    /// Return the names of the attributes.
+    fn attribute_names(&self) -> Result<Vec<String>> {
+        attribute::names(self)
+    }
+
+    /// Delete an attribute.
+    fn delete_attribute(&self, name: &str) -> Result<()> {
+        attribute::delete(self, name)
+    }
+
+    /// Read an attribute.
+    ///
+    /// The function converts the content of an existing attribute into the
+    /// requested type, provided that the stored datatype is compatible.
+    fn read_attribute<T: FromData>(&self, name: &str) -> Result<T> where Self: Sized {
+        T::from_data(try!(try!(attribute::open(self, name)).read()))
+    }
+
+    /// Write an attribute.
+    ///
+    /// If there exists an attribute with the same name, it will be replaced.
+    /// Single values are stored in scalar attributes, and empty sequences are
+    /// stored in null ones. Other data without elements keep their dimensions.
+    fn write_attribute<T: IntoData>(&self, name: &str, data: T) -> Result<()>
+        where Self: Sized
+    {
+        let data = try!(data.into_data());
+        if try!(attribute::exists(self, name)) {
+            try!(attribute::delete(self, name));
+        }
+        let dataspace = if data.dimensions() == [0] {
+            try!(dataspace::new_null())
+        } else {
+            try!(dataspace::new(data.dimensions()))
+        };
+        let attribute = try!(attribute::new(self, name, &data.datatype(), &dataspace));
+        attribute.write(&data)
+    }
+}
+
+impl<'l, T: ?Sized + Object> Object for &'l T {
+}
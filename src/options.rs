@@ -0,0 +1,170 @@
+use ffi;
+use libc;
+
+use dataspace::Dataspace;
+use datatype::Datatype;
+use plist::{self, PropertyList};
+use {Identity, Result};
+
+/// Dataset options.
+///
+/// Options control how the data are laid out and filtered when stored. All
+/// filters require a chunked layout; if no chunk dimensions are given, the
+/// whole dataset is stored as one chunk.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    chunk: Option<Vec<usize>>,
+    deflate: Option<u8>,
+    fletcher32: bool,
+    nbit: bool,
+    pack: bool,
+    scale_offset: Option<ScaleOffset>,
+    shuffle: bool,
+}
+
+/// A scale-offset filter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScaleOffset {
+    /// Integer data with a given number of bits, where zero lets the library
+    /// compute the minimal number of bits.
+    Integer(u32),
+    /// Floating-point data with a given number of decimal digits to retain.
+    Float(u32),
+}
+
+impl Options {
+    /// Create options.
+    #[inline]
+    pub fn new() -> Options {
+        Options::default()
+    }
+
+    /// Set the chunk dimensions.
+    pub fn chunk(mut self, dimensions: &[usize]) -> Options {
+        self.chunk = Some(dimensions.to_vec());
+        self
+    }
+
+    /// Enable the gzip compression with a level between 0 and 9.
+    pub fn deflate(mut self, level: u8) -> Options {
+        self.deflate = Some(level);
+        self
+    }
+
+    /// Enable the Fletcher-32 checksums.
+    pub fn fletcher32(mut self) -> Options {
+        self.fletcher32 = true;
+        self
+    }
+
+    /// Enable the n-bit compression.
+    pub fn nbit(mut self) -> Options {
+        self.nbit = true;
+        self
+    }
+
+    /// Store compound datatypes without padding.
+    ///
+    /// The data stay aligned in memory, and the library converts them when
+    /// writing and reading.
+    pub fn pack(mut self) -> Options {
+        self.pack = true;
+        self
+    }
+
+    /// Enable the scale-offset compression.
+    pub fn scale_offset(mut self, filter: ScaleOffset) -> Options {
+        self.scale_offset = Some(filter);
+        self
+    }
+
+    /// Enable the byte shuffling.
+    pub fn shuffle(mut self) -> Options {
+        self.shuffle = true;
+        self
+    }
+
+    fn filtered(&self) -> bool {
+        self.deflate.is_some() || self.fletcher32 || self.nbit || self.scale_offset.is_some() ||
+        self.shuffle
+    }
+}
+
+pub fn merge(options: Options, other: Options) -> Options {
+    match other.chunk {
+        Some(_) => other,
+        _ => Options { chunk: options.chunk, ..other },
+    }
+}
+
+pub fn new_dataset_create(options: &Options, dataspace: &Dataspace) -> Result<PropertyList> {
+    let dimensions = try!(dataspace.dimensions());
+    let list = try!(plist::new(unsafe { ffi::H5P_DATASET_CREATE }));
+    let chunk = match options.chunk {
+        Some(ref chunk) => Some(chunk.clone()),
+        _ if options.filtered() => {
+            if dimensions.iter().any(|&dimension| dimension == 0) {
+                raise!("filters require the chunk dimensions to be given for empty datasets");
+            }
+            Some(dimensions.to_vec())
+        },
+        _ => None,
+    };
+    if let Some(chunk) = chunk {
+        if dimensions.is_empty() || try!(dataspace.is_null()) {
+            raise!("scalar and null datasets cannot be chunked");
+        }
+        if chunk.len() != dimensions.len() {
+            raise!("the chunk should have the claimed number of dimensions");
+        }
+        let chunk = chunk.iter().map(|&dimension| dimension as ffi::hsize_t).collect::<Vec<_>>();
+        ok!(ffi::H5Pset_chunk(list.id(), chunk.len() as libc::c_int, chunk.as_ptr()),
+            "failed to set the chunk dimensions");
+    }
+    if options.shuffle {
+        try!(check(ffi::H5Z_FILTER_SHUFFLE, "shuffle"));
+        ok!(ffi::H5Pset_shuffle(list.id()), "failed to enable the shuffle filter");
+    }
+    if let Some(level) = options.deflate {
+        if level > 9 {
+            raise!("the compression level should be between 0 and 9");
+        }
+        try!(check(ffi::H5Z_FILTER_DEFLATE, "deflate"));
+        ok!(ffi::H5Pset_deflate(list.id(), level as libc::c_uint),
+            "failed to enable the deflate filter");
+    }
+    if options.nbit {
+        try!(check(ffi::H5Z_FILTER_NBIT, "n-bit"));
+        ok!(ffi::H5Pset_nbit(list.id()), "failed to enable the n-bit filter");
+    }
+    if let Some(filter) = options.scale_offset {
+        try!(check(ffi::H5Z_FILTER_SCALEOFFSET, "scale-offset"));
+        let (kind, factor) = match filter {
+            ScaleOffset::Integer(bits) => (ffi::H5Z_SO_INT, bits),
+            ScaleOffset::Float(digits) => (ffi::H5Z_SO_FLOAT_DSCALE, digits),
+        };
+        ok!(ffi::H5Pset_scaleoffset(list.id(), kind, factor as libc::c_int),
+            "failed to enable the scale-offset filter");
+    }
+    if options.fletcher32 {
+        try!(check(ffi::H5Z_FILTER_FLETCHER32, "Fletcher-32"));
+        ok!(ffi::H5Pset_fletcher32(list.id()), "failed to enable the Fletcher-32 filter");
+    }
+    Ok(list)
+}
+
+pub fn new_file_datatype(options: &Options, datatype: &Datatype) -> Result<Datatype> {
+    if options.pack { datatype.packed() } else { Ok(datatype.clone()) }
+}
+
+fn check(filter: ffi::H5Z_filter_t, name: &str) -> Result<()> {
+    if ok!(ffi::H5Zfilter_avail(filter), "failed to check the {} filter", name) <= 0 {
+        raise!("the {} filter is not available", name);
+    }
+    let mut flags = 0;
+    ok!(ffi::H5Zget_filter_info(filter, &mut flags), "failed to check the {} filter", name);
+    if flags & ffi::H5Z_FILTER_CONFIG_ENCODE_ENABLED == 0 {
+        raise!("the {} filter is not available for encoding", name);
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use ffi;
+
+use {ID, Result};
+
+pub struct PropertyList {
+    id: ID,
+}
+
+identity!(PropertyList);
+
+impl Drop for PropertyList {
+    fn drop(&mut self) {
+        whatever!(ffi::H5Pclose(self.id));
+    }
+}
+
+pub fn new(class: ID) -> Result<PropertyList> {
+    Ok(PropertyList { id: ok!(ffi::H5Pcreate(class), "failed to create a property list") })
+}
+
+pub fn new_core_file_access(backing_store: bool) -> Result<PropertyList> {
+    let list = try!(new(unsafe { ffi::H5P_FILE_ACCESS }));
+    ok!(ffi::H5Pset_fapl_core(list.id, 64 * 1024, backing_store as ffi::hbool_t),
+        "failed to set the core driver");
+    Ok(list)
+}
+
+pub fn new_link_create() -> Result<PropertyList> {
+    let list = try!(new(unsafe { ffi::H5P_LINK_CREATE }));
+    ok!(ffi::H5Pset_create_intermediate_group(list.id, 1),
+        "failed to enable the creation of intermediate groups");
+    Ok(list)
+}
+
+#[inline]
+pub fn from_raw(id: ID) -> PropertyList {
+    PropertyList { id: id }
+}
@@ -0,0 +1,71 @@
+use data::{Data, IntoDataMut};
+use dataset::{self, Dataset};
+use dataspace;
+use datatype::{self, Datatype};
+use {Location, Result};
+
+/// A reader.
+///
+/// Readers are suitable for loading large arrays.
+pub struct Reader {
+    dataset: Dataset,
+    datatype: Datatype,
+    dimensions: Vec<usize>,
+}
+
+impl Reader {
+    /// Create a reader.
+    pub fn new<T: Location>(location: T, name: &str) -> Result<Reader> {
+        let dataset = try!(Dataset::open(location, name));
+        let datatype = try!(datatype::native(&try!(dataset.datatype())));
+        let dimensions = try!(try!(dataset.space()).dimensions());
+        Ok(Reader { dataset: dataset, datatype: datatype, dimensions: dimensions })
+    }
+
+    /// Return the dimensions.
+    #[inline]
+    pub fn dimensions(&self) -> &[usize] {
+        &self.dimensions
+    }
+
+    /// Read data.
+    ///
+    /// The function reads a chunk of data at a particular position with a
+    /// particular size into a buffer, which should be large enough to
+    /// accommodate the chunk.
+    pub fn read<T: IntoDataMut>(&mut self, data: T, position: &[usize], size: &[usize])
+                                -> Result<()> {
+
+        let data = try!(data.into_data_mut());
+        if self.datatype != data.datatype() {
+            raise!("the data should have the stored datatype");
+        }
+        if self.dimensions.len() != position.len() {
+            raise!("the position should have the stored number of dimensions");
+        }
+        if self.dimensions.len() != size.len() {
+            raise!("the size should have the stored number of dimensions");
+        }
+        if product!(data.dimensions()) != product!(size) {
+            raise!("the data should have the claimed number of elements");
+        }
+        for i in 0..size.len() {
+            match position[i].checked_add(size[i]) {
+                Some(end) if end <= self.dimensions[i] => {},
+                _ => raise!("the chunk should fit within the stored dimensions"),
+            }
+        }
+
+        if product!(size) == 0 {
+            return Ok(());
+        }
+
+        let memory_space = try!(dataspace::new(size));
+        let file_space = try!(self.dataset.space());
+        if !size.is_empty() {
+            try!(file_space.select(position, size));
+        }
+
+        dataset::read_into(&self.dataset, data, &memory_space, &file_space)
+    }
+}
@@ -0,0 +1,356 @@
+use serde::ser::{self, Impossible, Serialize};
+use std::fmt::Display;
+use std::mem;
+
+use compound::{self, Sequence, Structure};
+use data::{Data, IntoData};
+use {Error, Location, Options, Result};
+
+/// A serializer.
+///
+/// Serializers are suitable for storing structural data.
+///
+/// Unit variants of enums are stored as enum datatypes whose values are the
+/// indices of the variants. Since `serde` reveals only the variant being
+/// serialized, the datatype lists only the variants present in the data, and
+/// so do other readers such as h5py.
+pub struct Serializer<'l> {
+    location: &'l Location,
+    name: Option<String>,
+    options: Options,
+    state: State,
+    stack: Vec<State>,
+}
+
+enum State {
+    Uncertain,
+    Array(Sequence),
+    Sequence(Sequence),
+    Structure(Structure),
+}
+
+impl<'l> Serializer<'l> {
+    /// Create a serializer.
+    ///
+    /// If there exists a dataset with the same name, it will be removed from
+    /// the file structure, and a new dataset will be created. This operation,
+    /// however, does not reclaim the corresponding space. See [Section
+    /// 5.5.2][1] in HDF5 User’s Guide for further details.
+    ///
+    /// [1]: https://www.hdfgroup.org/HDF5/doc/UG/10_Datasets.html#Allocation
+    pub fn new(location: &'l Location, name: &str) -> Serializer<'l> {
+        Serializer {
+            location: location,
+            name: Some(name.to_string()),
+            options: Options::new(),
+            state: State::Uncertain,
+            stack: vec![],
+        }
+    }
+
+    /// Set the options of the dataset to be created.
+    ///
+    /// Structures are laid out with the native alignment; see
+    /// `Options::pack` for storing them without padding.
+    pub fn options(mut self, options: Options) -> Serializer<'l> {
+        self.options = options;
+        self
+    }
+
+    fn element<T: Data>(&mut self, data: T) -> Result<()> {
+        match self.state {
+            State::Uncertain => match self.name.take() {
+                Some(ref name) => {
+                    Location::write_with_options(&self.location, name, data, self.options.clone())
+                },
+                _ => raise!("cannot write data without a name"),
+            },
+            State::Array(ref mut sequence) | State::Sequence(ref mut sequence) => {
+                sequence.push(data)
+            },
+            State::Structure(ref mut structure) => match self.name.take() {
+                Some(name) => structure.push(name, data),
+                _ => raise!("cannot write a field without a name"),
+            },
+        }
+    }
+
+    fn begin(&mut self, state: State) {
+        let state = mem::replace(&mut self.state, state);
+        self.stack.push(state);
+    }
+
+    fn end(&mut self) -> Result<()> {
+        let state = match self.stack.pop() {
+            Some(state) => state,
+            _ => raise!("found an unbalanced end of a sequence or structure"),
+        };
+        match mem::replace(&mut self.state, state) {
+            State::Array(sequence) => match self.state {
+                State::Uncertain => self.element(try!(sequence.coagulate())),
+                _ => self.element(try!(sequence.coagulate_array())),
+            },
+            State::Sequence(sequence) => match self.state {
+                State::Uncertain => self.element(try!(sequence.coagulate())),
+                _ => self.element(try!(sequence.coagulate_variable())),
+            },
+            State::Structure(structure) => self.element(try!(structure.coagulate())),
+            State::Uncertain => unreachable!(),
+        }
+    }
+
+    fn missing(&mut self) -> Result<()> {
+        match self.state {
+            State::Array(..) | State::Sequence(..) => {
+                raise!("cannot write a missing element of a sequence")
+            },
+            _ => {
+                self.name = None;
+                Ok(())
+            },
+        }
+    }
+
+    fn structure_field<T: ?Sized + Serialize>(&mut self, name: &str, value: &T) -> Result<()> {
+        let name = mem::replace(&mut self.name, Some(name.to_string()));
+        try!(value.serialize(&mut *self));
+        match mem::replace(&mut self.name, name) {
+            None => Ok(()),
+            _ => raise!("found a field without a value"),
+        }
+    }
+}
+
+impl ser::Error for Error {
+    #[inline]
+    fn custom<T: Display>(message: T) -> Error {
+        Error(message.to_string())
+    }
+}
+
+impl<'s, 'l> ser::Serializer for &'s mut Serializer<'l> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    #[inline]
+    fn serialize_bool(self, value: bool) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_i8(self, value: i8) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_i16(self, value: i16) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_i32(self, value: i32) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_i64(self, value: i64) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_u8(self, value: u8) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_u16(self, value: u16) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_u32(self, value: u32) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_u64(self, value: u64) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_f32(self, value: f32) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_f64(self, value: f64) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_char(self, value: char) -> Result<()> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn serialize_str(self, value: &str) -> Result<()> {
+        match self.state {
+            State::Uncertain => self.element(try!(value.into_data())),
+            _ => self.element(try!((&[value][..]).into_data())),
+        }
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        if let State::Uncertain = self.state {
+            return self.element(try!(value.into_data()));
+        }
+        let mut sequence = Sequence::new();
+        for &byte in value {
+            try!(sequence.push(byte));
+        }
+        self.element(try!(sequence.coagulate_variable()))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<()> {
+        self.missing()
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        raise!("HDF5 does not support units");
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<()> {
+        raise!("HDF5 does not support unit structs");
+    }
+
+    #[inline]
+    fn serialize_unit_variant(self, _: &'static str, index: u32, variant: &'static str)
+                              -> Result<()> {
+
+        self.element(try!(compound::variant(variant, index)))
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _: &'static str, value: &T)
+                                                       -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _: &'static str, _: u32,
+                                                        _: &'static str, _: &T) -> Result<()> {
+        raise!("HDF5 does not support enums with arguments");
+    }
+
+    #[inline]
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self> {
+        self.begin(State::Sequence(Sequence::new()));
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _: usize) -> Result<Self> {
+        self.begin(State::Array(Sequence::new()));
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self> {
+        self.begin(State::Array(Sequence::new()));
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(self, _: &'static str, _: u32, _: &'static str, _: usize)
+                               -> Result<Self::SerializeTupleVariant> {
+        raise!("HDF5 does not support enums with arguments");
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap> {
+        raise!("HDF5 does not support maps");
+    }
+
+    #[inline]
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self> {
+        self.begin(State::Structure(Structure::new()));
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(self, _: &'static str, _: u32, _: &'static str, _: usize)
+                                -> Result<Self::SerializeStructVariant> {
+        raise!("HDF5 does not support enum structs");
+    }
+}
+
+impl<'s, 'l> ser::SerializeSeq for &'s mut Serializer<'l> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Serializer::end(self)
+    }
+}
+
+impl<'s, 'l> ser::SerializeTuple for &'s mut Serializer<'l> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Serializer::end(self)
+    }
+}
+
+impl<'s, 'l> ser::SerializeTupleStruct for &'s mut Serializer<'l> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Serializer::end(self)
+    }
+}
+
+impl<'s, 'l> ser::SerializeStruct for &'s mut Serializer<'l> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, name: &'static str, value: &T)
+                                              -> Result<()> {
+        self.structure_field(name, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Serializer::end(self)
+    }
+}
@@ -1,9 +1,11 @@
+use std::mem;
+
 use data::{Data, IntoData};
 use dataset::{self, Dataset};
 use dataspace;
-use datatype::Datatype;
-use file::File;
+use datatype::{self, Datatype};
 use link::Link;
+use options::{self, Options};
 use {Location, Result};
 
 /// A writer.
@@ -13,36 +15,118 @@ pub struct Writer<'l> {
     state: State<'l>,
 }
 
+/// A mode of opening a dataset for writing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Create a new dataset and fail if there exists one with the same name.
+    Create,
+    /// Create a new dataset and remove the one with the same name if any.
+    Truncate,
+    /// Open an existing dataset and write into it in place.
+    OpenExisting,
+}
+
 enum State<'l> {
-    Setup { location: &'l File, name: String, dimensions: Vec<usize> },
+    Setup {
+        location: &'l Location,
+        name: String,
+        dimensions: Vec<usize>,
+        max_dimensions: Vec<Option<usize>>,
+        options: Options,
+        mode: Mode,
+    },
     Ready(Inner),
 }
 
 struct Inner {
     dataset: Dataset,
     datatype: Datatype,
-    dimensions: usize,
+    dimensions: Vec<usize>,
 }
 
 impl<'l> Writer<'l> {
     /// Create a writer.
     ///
-    /// If there exists a dataset with the same name, it will be removed from
-    /// the file structure, and a new dataset will be created. This operation,
-    /// however, does not reclaim the corresponding space. See [Section
-    /// 5.5.2][1] in HDF5 User’s Guide for further details.
+    /// By default, the writer operates in the `Truncate` mode: if there exists
+    /// a dataset with the same name, it will be removed from the file
+    /// structure, and a new dataset will be created. This operation, however,
+    /// does not reclaim the corresponding space. See [Section 5.5.2][1] in
+    /// HDF5 User’s Guide for further details.
+    ///
+    /// Empty dimensions result in a scalar dataset, and one dimension of size
+    /// zero results in a null one. Other dimensions without elements result in
+    /// a dataset of size zero with the same number of dimensions.
     ///
     /// [1]: https://www.hdfgroup.org/HDF5/doc/UG/10_Datasets.html#Allocation
-    pub fn new(file: &'l File, name: &str, dimensions: &[usize]) -> Writer<'l> {
+    pub fn new(location: &'l Location, name: &str, dimensions: &[usize]) -> Writer<'l> {
+        Writer {
+            state: State::Setup {
+                location: location,
+                name: name.to_string(),
+                dimensions: dimensions.to_vec(),
+                max_dimensions: dimensions.iter().map(|&dimension| Some(dimension)).collect(),
+                options: Options::new(),
+                mode: Mode::Truncate,
+            },
+        }
+    }
+
+    /// Create a writer for an extendible dataset.
+    ///
+    /// The first dimension is unlimited, and the data are stored in chunks of
+    /// the given dimensions. The dataset grows as new data are appended. See
+    /// `Writer::new` regarding existing datasets with the same name.
+    pub fn extendible(location: &'l Location, name: &str, dimensions: &[usize], chunk: &[usize])
+                      -> Writer<'l> {
+
         Writer {
             state: State::Setup {
-                location: file,
+                location: location,
                 name: name.to_string(),
                 dimensions: dimensions.to_vec(),
+                max_dimensions: dimensions.iter().enumerate().map(|(i, &dimension)| {
+                    if i == 0 { None } else { Some(dimension) }
+                }).collect(),
+                options: Options::new().chunk(chunk),
+                mode: Mode::Truncate,
             },
         }
     }
 
+    /// Set the mode of opening the dataset.
+    ///
+    /// In the `OpenExisting` mode, the dimensions and options given to the
+    /// writer are ignored except for the number of dimensions, which should
+    /// match the one of the existing dataset.
+    pub fn mode(mut self, mode: Mode) -> Writer<'l> {
+        if let State::Setup { mode: ref mut current, .. } = self.state {
+            *current = mode;
+        }
+        self
+    }
+
+    /// Set the options of the dataset to be created.
+    ///
+    /// The chunk dimensions given to `Writer::extendible` are overridden only
+    /// if the options specify chunk dimensions themselves.
+    pub fn options(mut self, options: Options) -> Writer<'l> {
+        if let State::Setup { options: ref mut current, .. } = self.state {
+            *current = options::merge(mem::replace(current, Options::new()), options);
+        }
+        self
+    }
+
+    /// Append data.
+    ///
+    /// The function extends the dataset along the first dimension and writes a
+    /// chunk of data with a particular size at the end. The rest of the
+    /// dimensions of the chunk should match those of the dataset.
+    pub fn append<T: IntoData>(&mut self, data: T, size: &[usize]) -> Result<()> {
+        let data = try!(data.into_data());
+        let inner = try!(self.ready(data.datatype()));
+        inner.append(data, size)
+    }
+
     /// Write data.
     ///
     /// The function writes a chunk of data at a particular position with a
@@ -52,47 +136,127 @@ impl<'l> Writer<'l> {
                               -> Result<()> {
 
         let data = try!(data.into_data());
-        let state = match self.state {
-            State::Ready(ref mut inner) => return inner.write(data, position, size),
-            State::Setup { location, ref name, ref dimensions } => {
-                State::Ready(try!(Inner::new(location, name, data.datatype(), dimensions)))
+        let inner = try!(self.ready(data.datatype()));
+        inner.write(data, position, size)
+    }
+
+    fn ready(&mut self, datatype: Datatype) -> Result<&mut Inner> {
+        let inner = match self.state {
+            State::Setup { location, ref name, ref dimensions, ref max_dimensions, ref options,
+                           mode } => {
+
+                Some(match mode {
+                    Mode::OpenExisting => try!(Inner::open(location, name, datatype, dimensions)),
+                    _ => try!(Inner::new(location, name, datatype, dimensions, max_dimensions,
+                                         options, mode)),
+                })
             },
+            _ => None,
         };
-        self.state = state;
-        self.write(data, position, size)
+        if let Some(inner) = inner {
+            self.state = State::Ready(inner);
+        }
+        match self.state {
+            State::Ready(ref mut inner) => Ok(inner),
+            _ => unreachable!(),
+        }
     }
 }
 
 impl Inner {
-    fn new<T: Location>(location: T, name: &str, datatype: Datatype, dimensions: &[usize])
+    fn new<T: Location>(location: T, name: &str, datatype: Datatype, dimensions: &[usize],
+                        max_dimensions: &[Option<usize>], options: &Options, mode: Mode)
                         -> Result<Inner> {
 
         if try!(Link::exists(&location, name)) {
+            if mode == Mode::Create {
+                raise!("there exists a dataset {:?}", name);
+            }
             try!(Link::delete(&location, name));
         }
-        let dataspace = try!(dataspace::new(dimensions));
-        let dataset = try!(dataset::new(&location, name, &datatype, &dataspace));
-        Ok(Inner { dataset: dataset, datatype: datatype, dimensions: dimensions.len() })
+        let fixed = max_dimensions.iter().all(|dimension| dimension.is_some());
+        let dataspace = if fixed && dimensions == [0] {
+            try!(dataspace::new_null())
+        } else {
+            try!(dataspace::new_extendible(dimensions, max_dimensions))
+        };
+        let dataset = try!(dataset::new(&location, name, &datatype, &dataspace, options));
+        Ok(Inner { dataset: dataset, datatype: datatype, dimensions: dimensions.to_vec() })
+    }
+
+    fn open<T: Location>(location: T, name: &str, datatype: Datatype, dimensions: &[usize])
+                         -> Result<Inner> {
+
+        let dataset = try!(Dataset::open(location, name));
+        if try!(datatype::native(&try!(dataset.datatype()))) != datatype {
+            raise!("the data should have the stored datatype");
+        }
+        let stored = try!(dataset.dimensions());
+        if stored.len() != dimensions.len() {
+            raise!("the claimed number of dimensions should match the stored one");
+        }
+        Ok(Inner { dataset: dataset, datatype: datatype, dimensions: stored })
+    }
+
+    fn append<T: Data>(&mut self, data: T, size: &[usize]) -> Result<()> {
+        if self.datatype != data.datatype() {
+            raise!("the data should have the claimed datatype");
+        }
+        if self.dimensions.len() != size.len() {
+            raise!("the size should have the claimed number of dimensions");
+        }
+        if self.dimensions.is_empty() {
+            raise!("the data should have at least one dimension to append along");
+        }
+        if self.dimensions[1..] != size[1..] {
+            raise!("the size should match the claimed dimensions except for the first one");
+        }
+        if product!(data.dimensions()) != product!(size) {
+            raise!("the data should have the claimed number of elements");
+        }
+
+        let mut position = vec![0; size.len()];
+        position[0] = self.dimensions[0];
+        let mut dimensions = self.dimensions.clone();
+        dimensions[0] = match dimensions[0].checked_add(size[0]) {
+            Some(dimension) => dimension,
+            _ => raise!("the dimensions of the dataset should not overflow"),
+        };
+        try!(self.dataset.resize(&dimensions));
+        self.dimensions = dimensions;
+
+        self.write(data, &position, size)
     }
 
     fn write<T: Data>(&mut self, data: T, position: &[usize], size: &[usize]) -> Result<()> {
         if self.datatype != data.datatype() {
             raise!("the data should have the claimed datatype");
         }
-        if self.dimensions != position.len() {
+        if self.dimensions.len() != position.len() {
             raise!("the position should have the claimed number of dimensions");
         }
-        if self.dimensions != size.len() {
+        if self.dimensions.len() != size.len() {
             raise!("the size should have the claimed number of dimensions");
         }
         if product!(data.dimensions()) != product!(size) {
             raise!("the data should have the claimed number of elements");
         }
+        for i in 0..size.len() {
+            match position[i].checked_add(size[i]) {
+                Some(end) if end <= self.dimensions[i] => {},
+                _ => raise!("the chunk should fit within the claimed dimensions"),
+            }
+        }
+        if product!(size) == 0 {
+            return Ok(());
+        }
 
         let memory_space = try!(dataspace::new(size));
         let file_space = try!(self.dataset.space());
-        try!(file_space.select(position, size));
+        if !size.is_empty() {
+            try!(file_space.select(position, size));
+        }
 
-        self.dataset.write(data, &memory_space, &file_space)
+        dataset::write(&self.dataset, data, &memory_space, &file_space)
     }
 }
@@ -0,0 +1,58 @@
+use hdf5::{File, Hyperslab};
+use ndarray::{arr2, Array2, Array3, ArrayD, IxDyn};
+use temporary::Directory;
+
+#[test]
+fn empty() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("a", &Array2::<f64>::zeros((0, 3))).unwrap();
+
+    assert_eq!(file.dataset("a").unwrap().dimensions().unwrap(), vec![0, 3]);
+    assert_eq!(file.read::<Array2<f64>>("a").unwrap().shape(), &[0, 3]);
+}
+
+#[test]
+fn hyperslab() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let array = Array2::from_shape_fn((4, 5), |(i, j)| (10 * i + j) as u16);
+    file.write("a", &array).unwrap();
+
+    let dataset = file.dataset("a").unwrap();
+    let hyperslab = Hyperslab::new(&[1, 0], &[2, 3]).stride(&[2, 2]);
+    let value = dataset.read_hyperslab::<Array2<u16>>(&hyperslab).unwrap();
+    assert_eq!(value, arr2(&[[10, 12, 14], [30, 32, 34]]));
+}
+
+#[test]
+fn read() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("a", &vec![vec![1.0f64, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+
+    let array = file.read::<Array2<f64>>("a").unwrap();
+    assert_eq!(array, arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+    let array = file.read::<ArrayD<f64>>("a").unwrap();
+    assert_eq!(array.shape(), &[2, 3]);
+    assert!(file.read::<Array3<f64>>("a").is_err());
+}
+
+#[test]
+fn write() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let array = arr2(&[[1i32, 2, 3], [4, 5, 6]]);
+
+    file.write("a", &array).unwrap();
+    assert_eq!(file.dataset("a").unwrap().dimensions().unwrap(), vec![2, 3]);
+    assert_eq!(file.read::<Vec<i32>>("a").unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+    file.write("b", array.t()).unwrap();
+    assert_eq!(file.dataset("b").unwrap().dimensions().unwrap(), vec![3, 2]);
+    assert_eq!(file.read::<Vec<i32>>("b").unwrap(), vec![1, 4, 2, 5, 3, 6]);
+
+    let array = ArrayD::<u8>::zeros(IxDyn(&[2, 1, 2]));
+    file.write("c", &array).unwrap();
+    assert_eq!(file.dataset("c").unwrap().dimensions().unwrap(), vec![2, 1, 2]);
+}
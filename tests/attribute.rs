@@ -0,0 +1,73 @@
+use hdf5::{Dataset, File, Group, Object};
+use temporary::Directory;
+
+#[test]
+fn dataset() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("foo", &vec![1.0, 2.0, 3.0]).unwrap();
+
+    let dataset = Dataset::open(&file, "foo").unwrap();
+    dataset.write_attribute("units", "meters").unwrap();
+    dataset.write_attribute("scale", 0.5f64).unwrap();
+
+    assert_eq!(dataset.read_attribute::<String>("units").unwrap(), "meters");
+    assert_eq!(dataset.read_attribute::<f64>("scale").unwrap(), 0.5);
+}
+
+#[test]
+fn delete() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    file.write_attribute("foo", 42).unwrap();
+    file.write_attribute("bar", 69).unwrap();
+    file.delete_attribute("foo").unwrap();
+
+    assert_eq!(file.attribute_names().unwrap(), vec!["bar".to_string()]);
+    assert!(file.read_attribute::<i32>("foo").is_err());
+    assert!(file.delete_attribute("foo").is_err());
+}
+
+#[test]
+fn file() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    file.write_attribute("version", 42u32).unwrap();
+    file.write_attribute("version", 69u32).unwrap();
+    file.write_attribute("scales", &vec![1.0f32, 2.0, 3.0]).unwrap();
+
+    assert_eq!(file.read_attribute::<u32>("version").unwrap(), 69);
+    assert_eq!(file.read_attribute::<Vec<f32>>("scales").unwrap(), vec![1.0, 2.0, 3.0]);
+    assert_eq!(file.attribute_names().unwrap(), vec!["scales".to_string(),
+                                                     "version".to_string()]);
+}
+
+#[test]
+fn group() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    {
+        let group = Group::new(&file, "a/b").unwrap();
+        group.write_attribute("flag", true).unwrap();
+    }
+    {
+        let group = Group::open(&file, "a/b").unwrap();
+        assert_eq!(group.read_attribute::<bool>("flag").unwrap(), true);
+    }
+}
+
+#[test]
+fn null() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    file.write_attribute("foo", &Vec::<u16>::new()).unwrap();
+    assert_eq!(file.read_attribute::<Vec<u16>>("foo").unwrap(), Vec::<u16>::new());
+    assert!(file.read_attribute::<u16>("foo").is_err());
+
+    file.write_attribute("bar", &Vec::<Vec<u16>>::new()).unwrap();
+    assert_eq!(file.read_attribute::<Vec<u16>>("bar").unwrap(), Vec::<u16>::new());
+}
@@ -0,0 +1,91 @@
+use hdf5::{File, Hyperslab, Layout, Reader, Shaped};
+use temporary::Directory;
+
+#[test]
+fn hyperslab() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let data = (0..12).collect::<Vec<u8>>();
+    file.write("foo", Shaped::new(&data, &[3, 4])).unwrap();
+
+    let dataset = file.dataset("foo").unwrap();
+    let hyperslab = Hyperslab::new(&[0, 1], &[2, 1]).block(&[1, 2]);
+    assert_eq!(hyperslab.dimensions(), vec![2, 2]);
+    assert_eq!(dataset.read_hyperslab::<Vec<u8>>(&hyperslab).unwrap(), vec![1, 2, 5, 6]);
+    assert!(dataset.read_hyperslab::<Vec<u8>>(&Hyperslab::new(&[0], &[1])).is_err());
+}
+
+#[test]
+fn introspection() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("foo", &vec![1.0f64, 2.0, 3.0]).unwrap();
+
+    let dataset = file.dataset("foo").unwrap();
+    assert_eq!(dataset.dimensions().unwrap(), vec![3]);
+    assert_eq!(dataset.max_dimensions().unwrap(), vec![Some(3)]);
+    assert_eq!(dataset.datatype().unwrap().size().unwrap(), 8);
+    assert_eq!(dataset.storage_size().unwrap(), 3 * 8);
+    assert_eq!(dataset.layout().unwrap(), Layout::Contiguous);
+    assert_eq!(dataset.chunk().unwrap(), None);
+
+    assert!(file.dataset("bar").is_err());
+}
+
+#[test]
+fn null() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("foo", &Vec::<f64>::new()).unwrap();
+
+    let dataset = file.dataset("foo").unwrap();
+    assert!(dataset.space().unwrap().is_null().unwrap());
+    assert_eq!(dataset.dimensions().unwrap(), vec![0]);
+    assert_eq!(file.read::<Vec<f64>>("foo").unwrap(), Vec::<f64>::new());
+    assert!(file.read::<f64>("foo").is_err());
+
+    let mut reader = Reader::new(&file, "foo").unwrap();
+    assert_eq!(reader.dimensions(), &[0]);
+    reader.read(&mut Vec::<f64>::new(), &[0], &[0]).unwrap();
+    assert!(reader.read(&mut vec![0.0], &[0], &[1]).is_err());
+}
+
+#[test]
+fn empty() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let empty: &[f64] = &[];
+    file.write("foo", Shaped::new(empty, &[0, 3])).unwrap();
+    file.write("bar", &Vec::<Vec<u8>>::new()).unwrap();
+
+    let dataset = file.dataset("foo").unwrap();
+    assert!(!dataset.space().unwrap().is_null().unwrap());
+    assert_eq!(dataset.dimensions().unwrap(), vec![0, 3]);
+    assert_eq!(file.read::<Vec<f64>>("foo").unwrap(), Vec::<f64>::new());
+
+    let mut reader = Reader::new(&file, "foo").unwrap();
+    assert_eq!(reader.dimensions(), &[0, 3]);
+    reader.read(&mut Vec::<f64>::new(), &[0, 0], &[0, 3]).unwrap();
+
+    assert_eq!(file.dataset("bar").unwrap().dimensions().unwrap(), vec![0, 0]);
+}
+
+#[test]
+fn scalar() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("foo", 42i32).unwrap();
+    file.write("bar", "baz").unwrap();
+
+    let dataset = file.dataset("foo").unwrap();
+    assert!(dataset.space().unwrap().is_scalar().unwrap());
+    assert_eq!(dataset.dimensions().unwrap(), Vec::<usize>::new());
+    assert_eq!(file.read::<i32>("foo").unwrap(), 42);
+    assert_eq!(file.read::<Vec<i32>>("foo").unwrap(), vec![42]);
+
+    assert!(file.dataset("bar").unwrap().space().unwrap().is_scalar().unwrap());
+    assert_eq!(file.read::<String>("bar").unwrap(), "baz");
+
+    file.write("baz", "").unwrap();
+    assert_eq!(file.read::<String>("baz").unwrap(), "");
+}
@@ -0,0 +1,85 @@
+use hdf5::{Dataspace, File, Hyperslab, Operator, Writer};
+use temporary::Directory;
+
+#[test]
+fn hyperslab() {
+    let space = Dataspace::new(&[10, 10]).unwrap();
+    assert_eq!(space.selection_size().unwrap(), 100);
+
+    let hyperslab = Hyperslab::new(&[1, 0], &[2, 3]).stride(&[5, 3]).block(&[2, 1]);
+    space.select_hyperslab(Operator::Set, &hyperslab).unwrap();
+    assert_eq!(space.selection_size().unwrap(), 12);
+    assert_eq!(space.selection_bounds().unwrap(), (vec![1, 0], vec![7, 6]));
+
+    space.select_hyperslab(Operator::Or, &Hyperslab::new(&[0, 0], &[2, 2])).unwrap();
+    assert_eq!(space.selection_size().unwrap(), 15);
+    space.select_hyperslab(Operator::NotB, &Hyperslab::new(&[0, 0], &[10, 1])).unwrap();
+    assert_eq!(space.selection_size().unwrap(), 10);
+    space.select_hyperslab(Operator::And, &Hyperslab::new(&[0, 0], &[5, 10])).unwrap();
+    assert_eq!(space.selection_size().unwrap(), 6);
+    space.select_hyperslab(Operator::Xor, &Hyperslab::new(&[0, 0], &[1, 2])).unwrap();
+    assert_eq!(space.selection_size().unwrap(), 6);
+
+    space.select_none().unwrap();
+    assert_eq!(space.selection_size().unwrap(), 0);
+    space.select_all().unwrap();
+    assert_eq!(space.selection_size().unwrap(), 100);
+
+    assert!(space.select_hyperslab(Operator::Set, &Hyperslab::new(&[0], &[1])).is_err());
+}
+
+#[test]
+fn null() {
+    let space = Dataspace::new_null().unwrap();
+    assert!(space.is_null().unwrap());
+    assert!(!space.is_scalar().unwrap());
+    assert_eq!(space.dimensions().unwrap(), vec![0]);
+    assert_eq!(space.selection_size().unwrap(), 0);
+}
+
+#[test]
+fn points() {
+    let space = Dataspace::new(&[4, 5]).unwrap();
+    space.select_points(&[[3, 1], [0, 4], [2, 2]]).unwrap();
+    assert_eq!(space.selection_size().unwrap(), 3);
+    assert_eq!(space.selection_bounds().unwrap(), (vec![0, 1], vec![3, 4]));
+    assert!(space.select_points(&[vec![1, 2, 3]]).is_err());
+}
+
+#[test]
+fn scalar() {
+    let space = Dataspace::new(&[]).unwrap();
+    assert!(space.is_scalar().unwrap());
+    assert!(!space.is_null().unwrap());
+    assert_eq!(space.dimensions().unwrap(), Vec::<usize>::new());
+    assert_eq!(space.selection_size().unwrap(), 1);
+}
+
+#[test]
+fn selection() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let data = (0..16).map(|value| value as f64).collect::<Vec<_>>();
+    let mut writer = Writer::new(&file, "a", &[4, 4]);
+    writer.write(&data, &[0, 0], &[4, 4]).unwrap();
+    drop(writer);
+
+    let dataset = file.dataset("a").unwrap();
+    let space = dataset.space().unwrap();
+    space.select_hyperslab(Operator::Set, &Hyperslab::new(&[0, 1], &[2, 2]).stride(&[2, 2]))
+         .unwrap();
+    assert_eq!(dataset.read_selection::<Vec<f64>>(&space).unwrap(), vec![1.0, 3.0, 9.0, 11.0]);
+
+    space.select_points(&[[3, 3], [0, 0]]).unwrap();
+    assert_eq!(dataset.read_selection::<Vec<f64>>(&space).unwrap(), vec![15.0, 0.0]);
+    dataset.write_selection(&vec![-1.0, -2.0], &space).unwrap();
+    assert!(dataset.write_selection(&vec![-1.0], &space).is_err());
+    assert!(dataset.write_selection(&vec![-1.0, -2.0, -3.0], &space).is_err());
+
+    let values = file.read::<Vec<f64>>("a").unwrap();
+    assert_eq!((values[0], values[15]), (-2.0, -1.0));
+
+    let space = Dataspace::new(&[2, 2]).unwrap();
+    assert!(dataset.read_selection::<Vec<f64>>(&space).is_err());
+}
@@ -0,0 +1,88 @@
+use hdf5::{Class, Datatype, File, Member, Order};
+use temporary::Directory;
+
+#[test]
+fn array() {
+    let datatype = Datatype::new_array(&Datatype::new_float(4).unwrap(), &[3, 3]).unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Array);
+    assert_eq!(datatype.dimensions().unwrap(), vec![3, 3]);
+    assert_eq!(datatype.size().unwrap(), 36);
+    assert!(Datatype::new_array(&Datatype::new_float(4).unwrap(), &[0]).is_err());
+}
+
+#[test]
+fn compound() {
+    let members = vec![
+        Member { name: "foo".to_string(), offset: 0, datatype: Datatype::new_float(8).unwrap() },
+        Member { name: "bar".to_string(), offset: 8, datatype: Datatype::new_string(3).unwrap() },
+    ];
+    let datatype = Datatype::new_compound(16, &members).unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Compound);
+    assert_eq!(datatype.size().unwrap(), 16);
+
+    let members = datatype.members().unwrap();
+    assert_eq!(members.len(), 2);
+    assert_eq!((&members[0].name[..], members[0].offset), ("foo", 0));
+    assert_eq!((&members[1].name[..], members[1].offset), ("bar", 8));
+    assert_eq!(members[0].datatype.class().unwrap(), Class::Float);
+    assert_eq!(members[1].datatype.class().unwrap(), Class::String);
+    assert_eq!(members[1].datatype.size().unwrap(), 3);
+    assert!(datatype.variants().is_err());
+}
+
+#[test]
+fn packed() {
+    let members = vec![
+        Member {
+            name: "foo".to_string(),
+            offset: 0,
+            datatype: Datatype::new_integer(1, false).unwrap(),
+        },
+        Member { name: "bar".to_string(), offset: 8, datatype: Datatype::new_float(8).unwrap() },
+    ];
+    let datatype = Datatype::new_compound(16, &members).unwrap();
+    let packed = datatype.packed().unwrap();
+    assert_eq!(packed.size().unwrap(), 9);
+    assert_eq!(packed.members().unwrap()[1].offset, 1);
+    assert_eq!(datatype.size().unwrap(), 16);
+
+    let datatype = Datatype::new_float(8).unwrap();
+    assert!(datatype.packed().unwrap() == datatype);
+}
+
+#[test]
+fn stored() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("a", 42i16).unwrap();
+    file.write("b", 42u64).unwrap();
+    file.write("c", 42f32).unwrap();
+
+    let datatype = file.dataset("a").unwrap().datatype().unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Integer);
+    assert_eq!(datatype.is_signed().unwrap(), true);
+    assert_eq!(datatype.precision().unwrap(), 16);
+    assert_eq!(datatype.order().unwrap(), if cfg!(target_endian = "little") {
+        Order::LittleEndian
+    } else {
+        Order::BigEndian
+    });
+
+    let datatype = file.dataset("b").unwrap().datatype().unwrap();
+    assert_eq!(datatype.is_signed().unwrap(), false);
+    assert_eq!(datatype.size().unwrap(), 8);
+    assert!(datatype == Datatype::new_integer(8, false).unwrap());
+
+    let datatype = file.dataset("c").unwrap().datatype().unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Float);
+    assert!(datatype.is_signed().is_err());
+    assert!(datatype != Datatype::new_float(8).unwrap());
+}
+
+#[test]
+fn variable_length() {
+    let datatype = Datatype::new_vlen(&Datatype::new_integer(4, true).unwrap()).unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Vlen);
+    assert_eq!(datatype.base().unwrap().class().unwrap(), Class::Integer);
+    assert_eq!(datatype.base().unwrap().size().unwrap(), 4);
+}
@@ -0,0 +1,365 @@
+use hdf5::{Class, Encoder, File, Options};
+use rustc_serialize::{Decodable, Decoder, Encodable};
+use std::collections::HashMap;
+use temporary::Directory;
+
+macro_rules! test(
+    ($($name:ident := $value:expr => $kind:ty,)*) => ({
+        let directory = Directory::new("hdf5").unwrap();
+        let file = File::new(directory.join("data.h5")).unwrap();
+        $({
+            let value = $value;
+            file.encode(stringify!($name), &value).unwrap();
+            assert_eq!(file.decode::<$kind>(stringify!($name)).unwrap(), value);
+        })*
+    });
+);
+
+#[test]
+fn array() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Foo {
+        a: (f64, f64, f64),
+        b: ((u8, u8), (u8, u8)),
+    }
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let foo = Foo { a: (42.0, 69.0, 0.5), b: ((1, 2), (3, 4)) };
+    file.encode("foo", &foo).unwrap();
+
+    let members = file.dataset("foo").unwrap().datatype().unwrap().members().unwrap();
+    assert_eq!(members[0].datatype.class().unwrap(), Class::Array);
+    assert_eq!(members[0].datatype.dimensions().unwrap(), vec![3]);
+    assert_eq!(members[1].datatype.dimensions().unwrap(), vec![2, 2]);
+    assert_eq!(file.decode::<Foo>("foo").unwrap(), foo);
+    assert!(file.decode::<(f64, f64)>("foo").is_err());
+}
+
+#[test]
+fn boolean() {
+    test!(
+        a := true => bool,
+        b := false => bool,
+    );
+}
+
+#[test]
+fn compound() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Foo {
+        a: f64,
+        b: i64,
+        c: Vec<u64>,
+        d: [usize; 2],
+        e: Bar,
+    }
+
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Bar {
+        a: f32,
+        b: u32,
+        c: Vec<isize>,
+    }
+
+    test!(
+        foo := Foo {
+            a: 42.0,
+            b: 42,
+            c: vec![42, 69],
+            d: [42, 69],
+            e: Bar {
+                a: 42.0,
+                b: 42,
+                c: vec![42, 69],
+            },
+        } => Foo,
+    );
+}
+
+#[test]
+fn compound_mismatch() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    #[derive(RustcEncodable)]
+    struct Foo {
+        a: f64,
+        b: i64,
+    }
+
+    #[derive(Debug, RustcDecodable)]
+    #[allow(dead_code)]
+    struct Bar {
+        a: f64,
+        c: i64,
+    }
+
+    #[derive(Debug, RustcDecodable)]
+    #[allow(dead_code)]
+    struct Baz {
+        a: f64,
+        b: f32,
+    }
+
+    file.encode("foo", &Foo { a: 42.0, b: 69 }).unwrap();
+    assert!(file.decode::<Bar>("foo").is_err());
+    assert!(file.decode::<Baz>("foo").is_err());
+    assert!(file.decode::<f64>("foo").is_err());
+}
+
+#[test]
+fn compound_vector() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Complex {
+        re: f64,
+        im: f64,
+    }
+
+    test!(
+        a := vec![Complex { re: 42.0, im: 69.0 }, Complex { re: 69.0, im: 42.0 }]
+             => Vec<Complex>,
+    );
+}
+
+#[test]
+fn compound_alignment() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Foo {
+        a: u8,
+        b: f64,
+        c: u16,
+        d: String,
+    }
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let foo = Foo { a: 42, b: 69.0, c: 42, d: "foo".to_string() };
+    file.encode("a", &foo).unwrap();
+    foo.encode(&mut Encoder::new(&file, "b").options(Options::new().pack())).unwrap();
+
+    let datatype = file.dataset("a").unwrap().datatype().unwrap();
+    let offsets = datatype.members().unwrap().iter().map(|member| member.offset)
+                                                   .collect::<Vec<_>>();
+    assert_eq!(offsets, vec![0, 8, 16, 24]);
+    assert_eq!(datatype.size().unwrap(), 32);
+
+    let datatype = file.dataset("b").unwrap().datatype().unwrap();
+    let offsets = datatype.members().unwrap().iter().map(|member| member.offset)
+                                                   .collect::<Vec<_>>();
+    assert_eq!(offsets, vec![0, 1, 9, 11]);
+    assert_eq!(datatype.size().unwrap(), 19);
+
+    assert_eq!(file.decode::<Foo>("a").unwrap(), foo);
+    assert_eq!(file.decode::<Foo>("b").unwrap(), foo);
+}
+
+#[test]
+fn enumeration() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    enum Foo {
+        Bar,
+        Baz,
+        Qux,
+    }
+
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Bar {
+        a: Foo,
+        b: Vec<Foo>,
+    }
+
+    test!(
+        a := Foo::Bar => Foo,
+        b := Foo::Baz => Foo,
+        c := Foo::Qux => Foo,
+        d := vec![Foo::Qux, Foo::Bar, Foo::Qux] => Vec<Foo>,
+        e := Bar { a: Foo::Baz, b: vec![Foo::Bar, Foo::Qux] } => Bar,
+    );
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.encode("a", &vec![Foo::Qux, Foo::Bar, Foo::Qux]).unwrap();
+    let datatype = file.dataset("a").unwrap().datatype().unwrap();
+    assert_eq!(datatype.variants().unwrap(), vec!["Bar", "Qux"]);
+}
+
+#[test]
+fn enumeration_by_name() {
+    #[derive(RustcEncodable)]
+    #[allow(dead_code)]
+    enum Foo {
+        Bar,
+        Baz,
+        Qux,
+    }
+
+    #[derive(Debug, PartialEq, RustcDecodable)]
+    enum Reordered {
+        Qux,
+        Bar,
+    }
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    file.encode("a", &Foo::Qux).unwrap();
+    file.encode("b", &Foo::Baz).unwrap();
+    file.write("c", 1usize).unwrap();
+
+    let datatype = file.dataset("a").unwrap().datatype().unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Enum);
+    assert_eq!(datatype.variants().unwrap(), vec!["Qux"]);
+    assert_eq!(file.decode::<Reordered>("a").unwrap(), Reordered::Qux);
+    assert!(file.decode::<Reordered>("b").is_err());
+    assert_eq!(file.decode::<Reordered>("c").unwrap(), Reordered::Bar);
+}
+
+#[test]
+fn numeric_scalar() {
+    test!(
+        a := 42f32 => f32,
+        b := 42f64 => f64,
+
+        c := 42i8 => i8,
+        d := 42u8 => u8,
+
+        e := 42i16 => i16,
+        f := 42u16 => u16,
+
+        g := 42i32 => i32,
+        h := 42u32 => u32,
+
+        i := 42i64 => i64,
+        j := 42u64 => u64,
+
+        k := 42isize => isize,
+        l := 42usize => usize,
+    );
+}
+
+#[test]
+fn numeric_vector() {
+    test!(
+        a := vec![42f32, 69f32] => Vec<f32>,
+        b := vec![42f64, 69f64] => Vec<f64>,
+
+        c := vec![42i8, 69i8] => Vec<i8>,
+        d := vec![42u8, 69u8] => Vec<u8>,
+
+        e := vec![42i16, 69i16] => Vec<i16>,
+        f := vec![42u16, 69u16] => Vec<u16>,
+
+        g := vec![42i32, 69i32] => Vec<i32>,
+        h := vec![42u32, 69u32] => Vec<u32>,
+
+        i := vec![42i64, 69i64] => Vec<i64>,
+        j := vec![42u64, 69u64] => Vec<u64>,
+
+        k := vec![42isize, 69isize] => Vec<isize>,
+        l := vec![42usize, 69usize] => Vec<usize>,
+    );
+}
+
+#[test]
+fn option() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Foo {
+        a: Option<f64>,
+        b: f64,
+    }
+
+    test!(
+        a := Some(42.0) => Option<f64>,
+        b := Option::None::<u16> => Option<u16>,
+        c := Some(69u8) => Option<u8>,
+        d := Foo { a: Some(42.0), b: 69.0 } => Foo,
+        e := Foo { a: None, b: 69.0 } => Foo,
+    );
+}
+
+#[test]
+fn ragged() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Event {
+        id: u32,
+        hits: Vec<f64>,
+    }
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let events = vec![
+        Event { id: 1, hits: vec![] },
+        Event { id: 2, hits: vec![42.0, 69.0, 0.5] },
+        Event { id: 3, hits: vec![1.0] },
+    ];
+    file.encode("a", &events).unwrap();
+
+    let dataset = file.dataset("a").unwrap();
+    assert_eq!(dataset.dimensions().unwrap(), vec![3]);
+    let members = dataset.datatype().unwrap().members().unwrap();
+    assert_eq!(members[1].datatype.class().unwrap(), Class::Vlen);
+    assert_eq!(members[1].datatype.base().unwrap().class().unwrap(), Class::Float);
+    assert_eq!(file.decode::<Vec<Event>>("a").unwrap(), events);
+}
+
+#[test]
+fn text() {
+    #[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
+    struct Foo {
+        a: String,
+        b: Vec<String>,
+    }
+
+    test!(
+        a := '界' => char,
+        b := "Hello, 世界!".to_string() => String,
+        c := vec!["a".to_string(), "bc".to_string(), "".to_string()] => Vec<String>,
+        d := Foo { a: "世界".to_string(), b: vec!["Hello".to_string(), "!".to_string()] } => Foo,
+        e := vec![
+            Foo { a: "a".to_string(), b: vec!["b".to_string()] },
+            Foo { a: "cd".to_string(), b: vec!["ef".to_string()] },
+        ] => Vec<Foo>,
+        f := "".to_string() => String,
+    );
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.encode("a", "foo").unwrap();
+    file.write("b", "foo").unwrap();
+
+    let one = file.dataset("a").unwrap();
+    let other = file.dataset("b").unwrap();
+    assert!(one.space().unwrap().is_scalar().unwrap());
+    assert!(one.datatype().unwrap() == other.datatype().unwrap());
+    assert_eq!(file.read::<String>("a").unwrap(), "foo");
+    assert_eq!(file.decode::<String>("b").unwrap(), "foo");
+}
+
+#[test]
+fn unsupported() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Foo(u8);
+
+    impl Decodable for Foo {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Foo, D::Error> {
+            decoder.read_enum("Foo", |decoder| {
+                decoder.read_enum_variant(&["Bar"], |decoder, _| {
+                    decoder.read_enum_variant_arg(0, |decoder| Ok(Foo(try!(u8::decode(decoder)))))
+                })
+            })
+        }
+    }
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.write("a", 0usize).unwrap();
+
+    assert!(file.decode::<()>("a").is_err());
+    assert!(file.decode::<Foo>("a").is_err());
+    assert!(file.decode::<HashMap<String, usize>>("a").is_err());
+}
@@ -0,0 +1,106 @@
+use hdf5::{Class, Data, Datatype, File, H5Type, IntoData, Options};
+use temporary::Directory;
+
+#[derive(Clone, Copy, Debug, H5Type, PartialEq)]
+#[repr(C)]
+struct Hit {
+    channel: u8,
+    energy: f64,
+    valid: bool,
+}
+
+#[derive(Clone, Copy, Debug, H5Type, PartialEq)]
+#[repr(C)]
+struct Event {
+    id: u32,
+    position: [f32; 3],
+    hit: Hit,
+}
+
+#[derive(Clone, Copy, Debug, H5Type, PartialEq)]
+#[repr(C)]
+struct Pair<T>(T, T);
+
+#[test]
+fn datatype() {
+    let datatype = Hit::new_datatype().unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Compound);
+    assert_eq!(datatype.size().unwrap(), 24);
+    let members = datatype.members().unwrap();
+    assert_eq!((&members[0].name[..], members[0].offset), ("channel", 0));
+    assert_eq!((&members[1].name[..], members[1].offset), ("energy", 8));
+    assert_eq!((&members[2].name[..], members[2].offset), ("valid", 16));
+    assert!(members[1].datatype == Datatype::new_float(8).unwrap());
+
+    let datatype = Event::new_datatype().unwrap();
+    let members = datatype.members().unwrap();
+    assert_eq!(members[1].datatype.class().unwrap(), Class::Array);
+    assert_eq!(members[1].datatype.dimensions().unwrap(), vec![3]);
+    assert_eq!(members[2].datatype.class().unwrap(), Class::Compound);
+    assert_eq!(members[2].offset, 16);
+
+    let datatype = Pair::<i16>::new_datatype().unwrap();
+    let members = datatype.members().unwrap();
+    assert_eq!((&members[1].name[..], members[1].offset), ("1", 2));
+}
+
+#[test]
+fn pack() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let hits = vec![
+        Hit { channel: 1, energy: 42.0, valid: true },
+        Hit { channel: 2, energy: 69.0, valid: false },
+    ];
+    file.write_with_options("a", &hits, Options::new().pack()).unwrap();
+
+    let datatype = file.dataset("a").unwrap().datatype().unwrap();
+    assert_eq!(datatype.size().unwrap(), 10);
+    assert_eq!(datatype.members().unwrap()[2].offset, 9);
+    assert_eq!(file.read::<Vec<Hit>>("a").unwrap(), hits);
+}
+
+#[test]
+fn read() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let hits = vec![
+        Hit { channel: 1, energy: 42.0, valid: true },
+        Hit { channel: 2, energy: 69.0, valid: false },
+    ];
+    file.write("a", &hits).unwrap();
+    assert_eq!(file.read::<Vec<Hit>>("a").unwrap(), hits);
+    assert!(file.read::<Hit>("a").is_err());
+
+    let event = Event { id: 42, position: [1.0, 2.0, 3.0], hit: hits[0] };
+    file.write("b", event).unwrap();
+    assert_eq!(file.read::<Event>("b").unwrap(), event);
+    file.write("b", &event).unwrap();
+    assert_eq!(file.read::<Event>("b").unwrap(), event);
+    assert!(file.read::<Hit>("b").is_err());
+
+    let pairs = vec![Pair(1i16, 2i16), Pair(3, 4)];
+    file.write("c", &pairs[..]).unwrap();
+    assert_eq!(file.read::<Vec<Pair<i16>>>("c").unwrap(), pairs);
+}
+
+#[test]
+fn write() {
+    let hits = [Hit { channel: 1, energy: 42.0, valid: true }; 3];
+    let data = (&hits[..]).into_data().unwrap();
+    assert_eq!(data.dimensions(), &[3]);
+    assert_eq!(data.as_bytes().len(), 3 * 24);
+    assert!(data.datatype() == Hit::new_datatype().unwrap());
+    assert!(data.as_bytes()[1..8].iter().all(|&byte| byte == 0));
+    assert!(data.as_bytes()[17..24].iter().all(|&byte| byte == 0));
+
+    let data = hits[0].into_data().unwrap();
+    assert_eq!(data.dimensions(), &[] as &[usize]);
+    assert_eq!(data.as_bytes()[..9], [1, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    assert!(Hit::PADDED);
+    assert!(Event::PADDED);
+    assert!(!Pair::<i16>::PADDED);
+}
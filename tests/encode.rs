@@ -1,5 +1,6 @@
-use hdf5::{Encoder, File};
+use hdf5::{Class, Encoder, File};
 use rustc_serialize::Encodable;
+use std::collections::HashMap;
 use temporary::Directory;
 
 macro_rules! test(
@@ -52,6 +53,7 @@ fn compound() {
         c: Vec<u64>,
         d: [usize; 2],
         e: Bar,
+        f: (u8, u8),
     }
 
     #[derive(RustcEncodable)]
@@ -71,10 +73,19 @@ fn compound() {
             b: 42,
             c: vec![42, 69],
         },
+        f: (42, 69),
     };
 
     let mut encoder = Encoder::new(&file, "foo");
     foo.encode(&mut encoder).unwrap();
+
+    let members = file.dataset("foo").unwrap().datatype().unwrap().members().unwrap();
+    let class = |name: &str| {
+        members.iter().find(|member| member.name == name).unwrap().datatype.class().unwrap()
+    };
+    assert_eq!(class("c"), Class::Vlen);
+    assert_eq!(class("d"), Class::Vlen);
+    assert_eq!(class("f"), Class::Array);
 }
 
 #[test]
@@ -152,3 +163,14 @@ fn text() {
         b := "Hello, 世界!",
     );
 }
+
+#[test]
+fn unsupported() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let mut map = HashMap::new();
+    map.insert("a".to_string(), 42usize);
+
+    assert!(file.encode("a", ()).is_err());
+    assert!(file.encode("b", &map).is_err());
+}
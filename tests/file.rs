@@ -0,0 +1,103 @@
+use hdf5::{File, Objects, OpenOptions, Scope};
+use std::fs;
+use temporary::Directory;
+
+#[test]
+fn in_memory() {
+    let bytes = {
+        let file = File::in_memory().unwrap();
+        file.write("foo", &vec![42.0, 69.0]).unwrap();
+        file.to_bytes().unwrap()
+    };
+    {
+        let file = File::from_bytes(&bytes).unwrap();
+        assert_eq!(file.read::<Vec<f64>>("foo").unwrap(), vec![42.0, 69.0]);
+        file.write("bar", 42).unwrap();
+        assert!(file.to_bytes().unwrap().len() > 0);
+    }
+
+    let directory = Directory::new("hdf5").unwrap();
+    let path = directory.join("data.h5");
+    {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).in_memory(true);
+        let file = options.open(&path).unwrap();
+        file.write("foo", 42).unwrap();
+    }
+    {
+        let file = OpenOptions::new().in_memory(false).open(&path).unwrap();
+        assert_eq!(file.read::<i32>("foo").unwrap(), 42);
+    }
+}
+
+#[test]
+fn open_options() {
+    let directory = Directory::new("hdf5").unwrap();
+    let path = directory.join("data.h5");
+
+    let error = OpenOptions::new().open(&path).err().unwrap();
+    assert!(error.to_string().contains("does not exist"));
+    assert!(OpenOptions::new().create(true).open(&path).is_err());
+
+    {
+        let file = OpenOptions::new().write(true).create_new(true).open(&path).unwrap();
+        file.write("foo", 42).unwrap();
+    }
+    {
+        let error = OpenOptions::new().write(true).create_new(true).open(&path).err().unwrap();
+        assert!(error.to_string().contains("already exists"));
+    }
+    {
+        let file = OpenOptions::new().open(&path).unwrap();
+        assert_eq!(file.read::<i32>("foo").unwrap(), 42);
+        assert!(file.write("bar", 69).is_err());
+    }
+    {
+        let file = OpenOptions::new().write(true).create(true).open(&path).unwrap();
+        file.write("bar", 69).unwrap();
+    }
+    {
+        let file = File::open(&path).unwrap();
+        assert_eq!(file.read::<i32>("foo").unwrap(), 42);
+        assert_eq!(file.read::<i32>("bar").unwrap(), 69);
+    }
+    {
+        let file = OpenOptions::new().write(true).truncate(true).open(&path).unwrap();
+        assert!(file.read::<i32>("foo").is_err());
+    }
+
+    let path = directory.join("other.h5");
+    let error = OpenOptions::new().write(true).truncate(true).open(&path).err().unwrap();
+    assert!(error.to_string().contains("does not exist"));
+    assert!(!path.exists());
+
+    fs::write(&path, b"foo").unwrap();
+    let error = OpenOptions::new().open(&path).err().unwrap();
+    assert!(error.to_string().contains("failed to open"));
+    assert!(OpenOptions::new().write(true).create(true).open(&path).is_err());
+    assert_eq!(fs::read(&path).unwrap(), b"foo");
+}
+
+#[test]
+fn metadata() {
+    let directory = Directory::new("hdf5").unwrap();
+    let path = directory.join("data.h5");
+
+    let file = File::new(&path).unwrap();
+    file.write("foo", &vec![42u8; 1024]).unwrap();
+    file.flush(Scope::Global).unwrap();
+
+    assert_eq!(file.path().unwrap(), path);
+    assert!(file.size().unwrap() >= 1024);
+    assert!(file.free_space().unwrap() < file.size().unwrap());
+    assert_eq!(file.object_count(Objects::Files).unwrap(), 1);
+    {
+        let _dataset = file.dataset("foo").unwrap();
+        assert_eq!(file.object_count(Objects::Datasets).unwrap(), 1);
+        assert_eq!(file.object_count(Objects::All).unwrap(), 2);
+    }
+    assert_eq!(file.object_count(Objects::Datasets).unwrap(), 0);
+
+    file.close().unwrap();
+    assert_eq!(File::open(&path).unwrap().read::<Vec<u8>>("foo").unwrap(), vec![42u8; 1024]);
+}
@@ -0,0 +1,56 @@
+use hdf5::{File, Group, Reader, Writer};
+use temporary::Directory;
+
+#[test]
+fn nested() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    {
+        let group = Group::new(&file, "a/b/c").unwrap();
+        group.write("foo", 42).unwrap();
+    }
+    {
+        let group = Group::open(&file, "a").unwrap();
+        let group = Group::open(&group, "b").unwrap();
+        assert_eq!(group.read::<i32>("c/foo").unwrap(), 42);
+    }
+
+    assert!(Group::new(&file, "a/b").is_err());
+    assert!(Group::open(&file, "a/d").is_err());
+}
+
+#[test]
+fn open_or_create() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    {
+        let group = Group::open_or_create(&file, "a/b").unwrap();
+        group.write("foo", &vec![42.0, 69.0]).unwrap();
+    }
+    {
+        let group = Group::open_or_create(&file, "a/b").unwrap();
+        assert_eq!(group.read::<Vec<f64>>("foo").unwrap(), vec![42.0, 69.0]);
+    }
+}
+
+#[test]
+fn writer() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let group = Group::new(&file, "run1").unwrap();
+
+    let mut writer = Writer::new(&group, "foo", &[2, 2]);
+    writer.write(&vec![1u8, 2, 3, 4], &[0, 0], &[2, 2]).unwrap();
+
+    file.write("run2/bar", 69u8).unwrap();
+
+    let mut reader = Reader::new(&file, "run1/foo").unwrap();
+    let mut buffer = vec![0u8; 2];
+    reader.read(&mut buffer, &[1, 0], &[1, 2]).unwrap();
+    assert_eq!(buffer, vec![3, 4]);
+
+    let group = Group::open(&file, "run2").unwrap();
+    assert_eq!(group.read::<u8>("bar").unwrap(), 69);
+}
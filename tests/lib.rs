@@ -1,12 +1,45 @@
 extern crate hdf5;
 extern crate temporary;
 
+#[macro_use]
+extern crate hdf5_derive;
+
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+
 #[cfg(feature = "serialize")]
 extern crate rustc_serialize;
 
+#[cfg(feature = "serde")]
+extern crate serde_bytes;
+
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+
+#[cfg(feature = "serialize")]
+mod decode;
 #[cfg(feature = "serialize")]
 mod encode;
 
+#[cfg(feature = "ndarray")]
+mod array;
+mod attribute;
+mod dataset;
+mod dataspace;
+mod datatype;
+mod derive;
+mod file;
+mod group;
+#[cfg(feature = "nalgebra")]
+mod matrix;
+mod read;
+mod reader;
+#[cfg(feature = "serde")]
+mod serde;
 mod write;
 
 #[test]
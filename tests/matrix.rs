@@ -0,0 +1,84 @@
+use hdf5::{File, Object};
+use nalgebra::{DMatrix, DVector, Matrix2x3, RowVector3, Vector3};
+use temporary::Directory;
+
+#[test]
+fn dynamic() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let matrix = DMatrix::from_row_slice(2, 3, &[1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let vector = DVector::from_vec(vec![1u32, 2, 3]);
+
+    file.write("a", &matrix).unwrap();
+    assert_eq!(file.dataset("a").unwrap().dimensions().unwrap(), vec![2, 3]);
+    assert_eq!(file.read::<Vec<f64>>("a").unwrap(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(file.read::<DMatrix<f64>>("a").unwrap(), matrix);
+    assert!(file.read::<DVector<f64>>("a").is_err());
+
+    file.write("b", &vector).unwrap();
+    assert_eq!(file.dataset("b").unwrap().dimensions().unwrap(), vec![3, 1]);
+    assert_eq!(file.read::<DVector<u32>>("b").unwrap(), vector);
+
+    file.write("c", &vec![1u32, 2, 3]).unwrap();
+    assert_eq!(file.read::<DVector<u32>>("c").unwrap(), vector);
+}
+
+#[test]
+fn empty() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let matrix = DMatrix::<f64>::zeros(0, 3);
+    let vector = DVector::<f64>::zeros(0);
+
+    file.write_matrix("a", &matrix).unwrap();
+    assert_eq!(file.dataset("a").unwrap().dimensions().unwrap(), vec![0, 3]);
+    assert_eq!(file.read_matrix::<DMatrix<f64>>("a").unwrap(), matrix);
+
+    file.write_matrix("b", &vector).unwrap();
+    assert_eq!(file.dataset("b").unwrap().dimensions().unwrap(), vec![0, 1]);
+    assert_eq!(file.read_matrix::<DVector<f64>>("b").unwrap(), vector);
+
+    file.write("c", &Vec::<f64>::new()).unwrap();
+    assert_eq!(file.read::<DVector<f64>>("c").unwrap(), vector);
+}
+
+#[test]
+fn fixed() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let matrix = Matrix2x3::new(1i16, 2, 3, 4, 5, 6);
+    let vector = Vector3::new(1.0f32, 2.0, 3.0);
+
+    file.write("a", &matrix).unwrap();
+    assert_eq!(file.read::<Matrix2x3<i16>>("a").unwrap(), matrix);
+    assert_eq!(file.read::<Vec<i16>>("a").unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+    file.write("b", &vector).unwrap();
+    assert_eq!(file.dataset("b").unwrap().dimensions().unwrap(), vec![3, 1]);
+    assert_eq!(file.read::<Vector3<f32>>("b").unwrap(), vector);
+    assert!(file.read::<RowVector3<f32>>("b").is_err());
+
+    file.write("c", &vector.transpose()).unwrap();
+    assert_eq!(file.dataset("c").unwrap().dimensions().unwrap(), vec![1, 3]);
+    assert_eq!(file.read::<RowVector3<f32>>("c").unwrap(), vector.transpose());
+}
+
+#[test]
+fn orientation() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let matrix = Matrix2x3::new(1u8, 2, 3, 4, 5, 6);
+
+    file.write_matrix("a", &matrix).unwrap();
+    let dataset = file.dataset("a").unwrap();
+    assert_eq!(dataset.read_attribute::<String>("orientation").unwrap(), "row-major");
+    assert_eq!(file.read_matrix::<Matrix2x3<u8>>("a").unwrap(), matrix);
+    assert_eq!(file.read_matrix::<DMatrix<u8>>("a").unwrap().shape(), (2, 3));
+
+    dataset.write_attribute("orientation", "column-major").unwrap();
+    assert!(file.read_matrix::<Matrix2x3<u8>>("a").is_err());
+
+    file.write("b", &matrix).unwrap();
+    assert!(file.read_matrix::<Matrix2x3<u8>>("b").is_err());
+    assert_eq!(file.read::<Matrix2x3<u8>>("b").unwrap(), matrix);
+}
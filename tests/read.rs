@@ -0,0 +1,118 @@
+use hdf5::File;
+use temporary::Directory;
+
+macro_rules! test(
+    ($($name:ident := $value:expr => $kind:ty,)*) => ({
+        let directory = Directory::new("hdf5").unwrap();
+        let file = File::new(directory.join("data.h5")).unwrap();
+        $({
+            let value = $value;
+            file.write(stringify!($name), &value).unwrap();
+            assert_eq!(file.read::<$kind>(stringify!($name)).unwrap(), value);
+        })*
+    });
+);
+
+#[test]
+fn boolean() {
+    test!(
+        a := true => bool,
+        b := false => bool,
+    );
+}
+
+#[test]
+fn mismatch() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    file.write("a", 42i32).unwrap();
+    assert!(file.read::<f64>("a").is_err());
+    assert!(file.read::<String>("a").is_err());
+
+    file.write("b", &vec![42u8, 69u8]).unwrap();
+    assert!(file.read::<u8>("b").is_err());
+    assert!(file.read::<bool>("b").is_err());
+
+    assert!(file.read::<u8>("c").is_err());
+}
+
+#[test]
+fn numeric_scalar() {
+    test!(
+        a := 42f32 => f32,
+        b := 42f64 => f64,
+
+        c := 42i8 => i8,
+        d := 42u8 => u8,
+
+        e := 42i16 => i16,
+        f := 42u16 => u16,
+
+        g := 42i32 => i32,
+        h := 42u32 => u32,
+
+        i := 42i64 => i64,
+        j := 42u64 => u64,
+
+        k := 42isize => isize,
+        l := 42usize => usize,
+    );
+}
+
+#[test]
+fn numeric_vector() {
+    test!(
+        a := vec![42f32, 69f32] => Vec<f32>,
+        b := vec![42f64, 69f64] => Vec<f64>,
+
+        c := vec![42i8, 69i8] => Vec<i8>,
+        d := vec![42u8, 69u8] => Vec<u8>,
+
+        e := vec![42i16, 69i16] => Vec<i16>,
+        f := vec![42u16, 69u16] => Vec<u16>,
+
+        g := vec![42i32, 69i32] => Vec<i32>,
+        h := vec![42u32, 69u32] => Vec<u32>,
+
+        i := vec![42i64, 69i64] => Vec<i64>,
+        j := vec![42u64, 69u64] => Vec<u64>,
+
+        k := vec![42isize, 69isize] => Vec<isize>,
+        l := vec![42usize, 69usize] => Vec<usize>,
+    );
+}
+
+#[test]
+fn reopen() {
+    let directory = Directory::new("hdf5").unwrap();
+    {
+        let file = File::new(directory.join("data.h5")).unwrap();
+        file.write("a", &vec![42.0, 69.0]).unwrap();
+    }
+    {
+        let file = File::open(directory.join("data.h5")).unwrap();
+        assert_eq!(file.read::<Vec<f64>>("a").unwrap(), vec![42.0, 69.0]);
+    }
+}
+
+#[test]
+fn text() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    file.write("a", '界').unwrap();
+    assert_eq!(file.read::<char>("a").unwrap(), '界');
+
+    file.write("b", "Hello, 世界!").unwrap();
+    assert_eq!(file.read::<String>("b").unwrap(), "Hello, 世界!");
+
+    let value = vec!["Hello".to_string(), ",".to_string(), "世界!".to_string()];
+    file.write("c", &value).unwrap();
+    assert_eq!(file.read::<Vec<String>>("c").unwrap(), value);
+    assert!(file.read::<String>("c").is_err());
+
+    file.write("d", &vec!["a", "bc"]).unwrap();
+    assert_eq!(file.read::<Vec<String>>("d").unwrap(), vec!["a", "bc"]);
+    assert_eq!(file.read::<Vec<String>>("b").unwrap(), vec!["Hello, 世界!"]);
+}
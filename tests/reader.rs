@@ -0,0 +1,41 @@
+use hdf5::{File, Reader, Writer};
+use temporary::Directory;
+
+#[test]
+fn chunk() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let data = (0..100).map(|i| i as f64).collect::<Vec<_>>();
+    let mut writer = Writer::new(&file, "foo", &[10, 10]);
+    writer.write(&data, &[0, 0], &[10, 10]).unwrap();
+
+    let mut reader = Reader::new(&file, "foo").unwrap();
+    assert_eq!(reader.dimensions(), &[10, 10]);
+
+    let mut buffer = vec![0.0; 2 * 3];
+    reader.read(&mut buffer, &[4, 2], &[2, 3]).unwrap();
+    assert_eq!(buffer, vec![42.0, 43.0, 44.0, 52.0, 53.0, 54.0]);
+
+    let mut buffer = vec![0.0; 10];
+    reader.read(&mut buffer, &[6, 0], &[1, 10]).unwrap();
+    assert_eq!(buffer, data[60..70].to_vec());
+}
+
+#[test]
+fn mismatch() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    file.write("foo", &vec![42i32; 10]).unwrap();
+    let mut reader = Reader::new(&file, "foo").unwrap();
+
+    assert!(reader.read(&mut vec![0f32; 2], &[0], &[2]).is_err());
+    assert!(reader.read(&mut vec![0i32; 2], &[0, 0], &[1, 2]).is_err());
+    assert!(reader.read(&mut vec![0i32; 3], &[0], &[2]).is_err());
+    assert!(reader.read(&mut vec![0i32; 2], &[9], &[2]).is_err());
+    assert!(reader.read(&mut vec![0i32; 2], &[8], &[2]).is_ok());
+    assert!(reader.read(&mut vec![0i32; 1], &[usize::MAX], &[1]).is_err());
+
+    assert!(Reader::new(&file, "bar").is_err());
+}
@@ -0,0 +1,299 @@
+use hdf5::{Class, File};
+use serde_bytes::{self, ByteBuf};
+use temporary::Directory;
+
+macro_rules! test(
+    ($($name:ident := $value:expr => $kind:ty,)*) => ({
+        let directory = Directory::new("hdf5").unwrap();
+        let file = File::new(directory.join("data.h5")).unwrap();
+        $({
+            let value = $value;
+            file.serialize(stringify!($name), &value).unwrap();
+            assert_eq!(file.deserialize::<$kind>(stringify!($name)).unwrap(), value);
+        })*
+    });
+);
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+enum Kind {
+    Bar,
+    Baz,
+    Qux,
+}
+
+#[test]
+fn array() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Foo {
+        a: [usize; 2],
+        b: [[f32; 3]; 3],
+        c: Vec<[u8; 2]>,
+    }
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    let foo = Foo {
+        a: [42, 69],
+        b: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        c: vec![[1, 2], [3, 4]],
+    };
+    file.serialize("foo", &foo).unwrap();
+
+    let members = file.dataset("foo").unwrap().datatype().unwrap().members().unwrap();
+    assert_eq!(members[0].datatype.class().unwrap(), Class::Array);
+    assert_eq!(members[0].datatype.dimensions().unwrap(), vec![2]);
+    assert_eq!(members[1].datatype.class().unwrap(), Class::Array);
+    assert_eq!(members[1].datatype.dimensions().unwrap(), vec![3, 3]);
+    assert_eq!(members[1].datatype.base().unwrap().class().unwrap(), Class::Float);
+    assert_eq!((members[1].offset, members[2].offset), (16, 52));
+    assert_eq!(members[2].datatype.class().unwrap(), Class::Vlen);
+    assert_eq!(members[2].datatype.base().unwrap().class().unwrap(), Class::Array);
+    assert_eq!(file.deserialize::<Foo>("foo").unwrap(), foo);
+}
+
+#[test]
+fn boolean() {
+    test!(
+        a := true => bool,
+        b := false => bool,
+    );
+}
+
+#[test]
+fn bytes() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Foo {
+        #[serde(with = "serde_bytes")]
+        a: Vec<u8>,
+        b: Vec<ByteBuf>,
+        c: (ByteBuf, ByteBuf),
+    }
+
+    test!(
+        a := ByteBuf::from(vec![1u8, 2, 3]) => ByteBuf,
+        b := vec![ByteBuf::from(vec![1u8, 2]), ByteBuf::from(vec![3u8])] => Vec<ByteBuf>,
+        c := Foo {
+            a: vec![1, 2, 3],
+            b: vec![ByteBuf::from(vec![4u8, 5]), ByteBuf::from(vec![6u8])],
+            c: (ByteBuf::from(vec![7u8]), ByteBuf::from(vec![8u8, 9])),
+        } => Foo,
+        d := vec![vec![1u8, 2], vec![3u8]] => Vec<Vec<u8>>,
+    );
+}
+
+#[test]
+fn compound() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Foo {
+        a: f64,
+        b: i64,
+        c: Vec<u64>,
+        d: [usize; 2],
+        e: Bar,
+        f: Kind,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Bar {
+        a: f32,
+        b: u32,
+        c: Vec<isize>,
+    }
+
+    test!(
+        foo := Foo {
+            a: 42.0,
+            b: 42,
+            c: vec![42, 69],
+            d: [42, 69],
+            e: Bar {
+                a: 42.0,
+                b: 42,
+                c: vec![42, 69],
+            },
+            f: Kind::Baz,
+        } => Foo,
+    );
+}
+
+#[test]
+fn compound_mismatch() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    #[derive(Serialize)]
+    struct Foo {
+        a: f64,
+        b: i64,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Bar {
+        a: f64,
+        c: i64,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Baz {
+        a: f64,
+        b: f32,
+    }
+
+    file.serialize("foo", &Foo { a: 42.0, b: 69 }).unwrap();
+    assert!(file.deserialize::<Bar>("foo").is_err());
+    assert!(file.deserialize::<Baz>("foo").is_err());
+    assert!(file.deserialize::<f64>("foo").is_err());
+}
+
+#[test]
+fn compound_vector() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Event {
+        kind: Kind,
+        energy: f64,
+    }
+
+    test!(
+        a := vec![
+            Event { kind: Kind::Bar, energy: 42.0 },
+            Event { kind: Kind::Qux, energy: 69.0 },
+            Event { kind: Kind::Bar, energy: 0.0 },
+        ] => Vec<Event>,
+    );
+}
+
+#[test]
+fn enumeration() {
+    test!(
+        a := Kind::Bar => Kind,
+        b := Kind::Baz => Kind,
+        c := vec![Kind::Qux, Kind::Bar, Kind::Qux] => Vec<Kind>,
+    );
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.serialize("a", Kind::Baz).unwrap();
+    file.serialize("b", &vec![Kind::Qux, Kind::Bar, Kind::Qux]).unwrap();
+
+    let datatype = file.dataset("a").unwrap().datatype().unwrap();
+    assert_eq!(datatype.class().unwrap(), Class::Enum);
+    assert_eq!(datatype.variants().unwrap(), vec!["Baz"]);
+    let datatype = file.dataset("b").unwrap().datatype().unwrap();
+    assert_eq!(datatype.variants().unwrap(), vec!["Bar", "Qux"]);
+}
+
+#[test]
+fn numeric_scalar() {
+    test!(
+        a := 42f32 => f32,
+        b := 42f64 => f64,
+
+        c := 42i8 => i8,
+        d := 42u8 => u8,
+
+        e := 42i16 => i16,
+        f := 42u16 => u16,
+
+        g := 42i32 => i32,
+        h := 42u32 => u32,
+
+        i := 42i64 => i64,
+        j := 42u64 => u64,
+    );
+}
+
+#[test]
+fn numeric_vector() {
+    test!(
+        a := vec![42f32, 69f32] => Vec<f32>,
+        b := vec![42f64, 69f64] => Vec<f64>,
+
+        c := vec![42i8, 69i8] => Vec<i8>,
+        d := vec![42u8, 69u8] => Vec<u8>,
+
+        e := vec![42i16, 69i16] => Vec<i16>,
+        f := vec![42u16, 69u16] => Vec<u16>,
+
+        g := vec![42i32, 69i32] => Vec<i32>,
+        h := vec![42u32, 69u32] => Vec<u32>,
+
+        i := vec![42i64, 69i64] => Vec<i64>,
+        j := vec![42u64, 69u64] => Vec<u64>,
+
+        k := [42f64, 69f64, 42f64] => [f64; 3],
+    );
+}
+
+#[test]
+fn option() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Foo {
+        a: Option<f64>,
+        b: f64,
+    }
+
+    test!(
+        a := Some(42.0) => Option<f64>,
+        b := Option::None::<u16> => Option<u16>,
+        c := Foo { a: Some(42.0), b: 69.0 } => Foo,
+        d := Foo { a: None, b: 69.0 } => Foo,
+    );
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    assert!(file.serialize("a", &vec![Some(42.0), None]).is_err());
+}
+
+#[test]
+fn ragged() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Event {
+        id: u32,
+        hits: Vec<f64>,
+    }
+
+    test!(
+        a := vec![
+            Event { id: 1, hits: vec![] },
+            Event { id: 2, hits: vec![42.0, 69.0, 0.5] },
+            Event { id: 3, hits: vec![1.0] },
+        ] => Vec<Event>,
+        b := vec![vec![1u8, 2], vec![], vec![3]] => Vec<Vec<u8>>,
+    );
+}
+
+#[test]
+fn text() {
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Foo {
+        a: String,
+        b: Vec<String>,
+    }
+
+    test!(
+        a := '界' => char,
+        b := "Hello, 世界!".to_string() => String,
+        c := vec!["a".to_string(), "bc".to_string(), "".to_string()] => Vec<String>,
+        d := Foo { a: "世界".to_string(), b: vec!["Hello".to_string(), "!".to_string()] } => Foo,
+        e := vec![
+            Foo { a: "a".to_string(), b: vec!["b".to_string()] },
+            Foo { a: "cd".to_string(), b: vec!["ef".to_string()] },
+        ] => Vec<Foo>,
+        f := "".to_string() => String,
+    );
+
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    file.serialize("a", "foo").unwrap();
+    file.write("b", "foo").unwrap();
+
+    let one = file.dataset("a").unwrap();
+    let other = file.dataset("b").unwrap();
+    assert!(one.space().unwrap().is_scalar().unwrap());
+    assert!(one.datatype().unwrap() == other.datatype().unwrap());
+    assert_eq!(file.read::<String>("a").unwrap(), "foo");
+    assert_eq!(file.deserialize::<String>("b").unwrap(), "foo");
+}
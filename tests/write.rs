@@ -1,4 +1,4 @@
-use hdf5::{Data, File, IntoData, Writer};
+use hdf5::{Data, File, IntoData, Layout, Mode, Options, ScaleOffset, Shaped, Writer};
 use temporary::Directory;
 
 macro_rules! test(
@@ -15,6 +15,32 @@ macro_rules! test(
     });
 );
 
+#[test]
+fn append() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    {
+        let mut writer = Writer::extendible(&file, "foo", &[0, 2], &[1, 2]);
+        writer.append(&vec![1u8, 2], &[1, 2]).unwrap();
+        writer.append(&vec![3u8, 4, 5, 6], &[2, 2]).unwrap();
+        assert!(writer.append(&vec![7u8, 8, 9], &[1, 3]).is_err());
+        assert!(writer.append(&vec![7u8, 8, 9], &[2, 2]).is_err());
+    }
+
+    let dataset = file.dataset("foo").unwrap();
+    assert_eq!(dataset.dimensions().unwrap(), vec![3, 2]);
+    assert_eq!(dataset.max_dimensions().unwrap(), vec![None, Some(2)]);
+    assert_eq!(dataset.layout().unwrap(), Layout::Chunked);
+    assert_eq!(dataset.chunk().unwrap(), Some(vec![1, 2]));
+    assert_eq!(file.read::<Vec<u8>>("foo").unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+    dataset.resize(&[4, 2]).unwrap();
+    assert_eq!(dataset.dimensions().unwrap(), vec![4, 2]);
+    assert!(dataset.resize(&[4, 3]).is_err());
+    assert!(dataset.resize(&[5]).is_err());
+}
+
 #[test]
 fn boolean() {
     test!(
@@ -23,6 +49,70 @@ fn boolean() {
     );
 }
 
+#[test]
+fn filters() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let data = (0..1000).map(|i| (i % 10) as f64).collect::<Vec<_>>();
+
+    let options = Options::new().chunk(&[100]).shuffle().deflate(6).fletcher32();
+    file.write_with_options("a", &data, options).unwrap();
+    assert_eq!(file.read::<Vec<f64>>("a").unwrap(), data);
+    let dataset = file.dataset("a").unwrap();
+    assert_eq!(dataset.chunk().unwrap(), Some(vec![100]));
+    assert!(dataset.storage_size().unwrap() < 1000 * 8);
+
+    let options = Options::new().scale_offset(ScaleOffset::Integer(0));
+    file.write_with_options("b", &vec![1i32, 2, 3], options).unwrap();
+    assert_eq!(file.dataset("b").unwrap().chunk().unwrap(), Some(vec![3]));
+    assert_eq!(file.read::<Vec<i32>>("b").unwrap(), vec![1, 2, 3]);
+
+    let mut writer = Writer::extendible(&file, "c", &[0], &[4]).options(Options::new().nbit());
+    writer.append(&vec![1u16, 2, 3], &[3]).unwrap();
+    assert_eq!(file.dataset("c").unwrap().chunk().unwrap(), Some(vec![4]));
+
+    assert!(file.write_with_options("d", 42, Options::new().deflate(10)).is_err());
+    assert!(file.write_with_options("d", &vec![1, 2], Options::new().chunk(&[1, 1])).is_err());
+}
+
+#[test]
+fn modes() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+
+    {
+        let mut writer = Writer::new(&file, "foo", &[2, 2]).mode(Mode::Create);
+        writer.write(&vec![1u8, 2, 3, 4], &[0, 0], &[2, 2]).unwrap();
+    }
+    {
+        let mut writer = Writer::new(&file, "foo", &[2, 2]).mode(Mode::Create);
+        assert!(writer.write(&vec![1u8, 2, 3, 4], &[0, 0], &[2, 2]).is_err());
+    }
+    {
+        let mut writer = Writer::new(&file, "foo", &[2, 2]).mode(Mode::OpenExisting);
+        writer.write(&vec![5u8, 6], &[1, 0], &[1, 2]).unwrap();
+        assert!(writer.write(&vec![7u8, 8], &[2, 0], &[1, 2]).is_err());
+    }
+    assert_eq!(file.read::<Vec<u8>>("foo").unwrap(), vec![1, 2, 5, 6]);
+    {
+        let mut writer = Writer::new(&file, "foo", &[4]).mode(Mode::OpenExisting);
+        assert!(writer.write(&vec![1u8, 2, 3, 4], &[0], &[4]).is_err());
+    }
+    {
+        let mut writer = Writer::new(&file, "foo", &[2, 2]).mode(Mode::OpenExisting);
+        assert!(writer.write(&vec![1.0f32, 2.0], &[0, 0], &[1, 2]).is_err());
+    }
+    {
+        let mut writer = Writer::new(&file, "bar", &[1]).mode(Mode::OpenExisting);
+        assert!(writer.write(42u8, &[0], &[1]).is_err());
+    }
+    {
+        let mut writer = Writer::new(&file, "foo", &[3]).mode(Mode::Truncate);
+        writer.write(&vec![1u8, 2, 3], &[0], &[3]).unwrap();
+    }
+    assert_eq!(file.read::<Vec<u8>>("foo").unwrap(), vec![1, 2, 3]);
+}
+
 #[test]
 fn numeric_scalar() {
     test!(
@@ -87,6 +177,8 @@ fn patch() {
     writer.write(&vec![0u8; 10 * 10], &[0, 0], &[10, 10]).unwrap();
     writer.write(42u8, &[4, 2], &[1, 1]).unwrap();
     writer.write(69u8, &[6, 9], &[1, 1]).unwrap();
+    assert!(writer.write(69u8, &[9, 10], &[1, 1]).is_err());
+    assert!(writer.write(69u8, &[usize::MAX, 0], &[1, 1]).is_err());
 }
 
 #[test]
@@ -106,6 +198,32 @@ fn reopen() {
     }
 }
 
+#[test]
+fn shape() {
+    let directory = Directory::new("hdf5").unwrap();
+    let file = File::new(directory.join("data.h5")).unwrap();
+    let data = vec![1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0];
+
+    file.write("a", Shaped::new(&data, &[2, 3])).unwrap();
+    assert_eq!(file.dataset("a").unwrap().dimensions().unwrap(), vec![2, 3]);
+    assert_eq!(file.read::<Vec<f64>>("a").unwrap(), data);
+
+    file.write("b", Shaped::new(&data, &[2, 3]).fortran()).unwrap();
+    assert_eq!(file.dataset("b").unwrap().dimensions().unwrap(), vec![2, 3]);
+    assert_eq!(file.read::<Vec<f64>>("b").unwrap(), vec![1.0, 3.0, 5.0, 2.0, 4.0, 6.0]);
+
+    file.write("c", &vec![vec![1u8, 2, 3], vec![4, 5, 6]]).unwrap();
+    assert_eq!(file.dataset("c").unwrap().dimensions().unwrap(), vec![2, 3]);
+    assert_eq!(file.read::<Vec<u8>>("c").unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+    file.write("d", &[[1i32, 2], [3, 4], [5, 6]]).unwrap();
+    assert_eq!(file.dataset("d").unwrap().dimensions().unwrap(), vec![3, 2]);
+    assert_eq!(file.read::<Vec<i32>>("d").unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+    assert!(file.write("e", Shaped::new(&data, &[4, 2])).is_err());
+    assert!(file.write("e", &vec![vec![1u8, 2], vec![3]]).is_err());
+}
+
 #[test]
 fn stress() {
     use std::sync::Arc;